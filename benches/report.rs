@@ -0,0 +1,31 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use err_report::{Report, ResultReportExt, StringError};
+use std::hint::black_box;
+
+fn fail(depth: usize) -> Result<(), Report<StringError>> {
+    if depth == 0 {
+        return Err(Report::msg("failed"));
+    }
+    fail(depth - 1).context("propagating")
+}
+
+fn layer(depth: usize) -> Result<(), Report<StringError>> {
+    if depth == 0 {
+        return Err(Report::msg("failed"));
+    }
+    layer(depth - 1).layer()
+}
+
+fn bench_report(c: &mut Criterion) {
+    c.bench_function("new", |b| {
+        b.iter(|| Report::new(black_box(StringError::new(String::new()))))
+    });
+    c.bench_function("new_with_context", |b| b.iter(|| fail(black_box(1))));
+    c.bench_function("layers_3", |b| b.iter(|| layer(black_box(2))));
+    c.bench_function("layers_30", |b| b.iter(|| layer(black_box(29))));
+    let report = layer(2).unwrap_err();
+    c.bench_function("display_3", |b| b.iter(|| black_box(&report).to_string()));
+}
+
+criterion_group!(benches, bench_report);
+criterion_main!(benches);
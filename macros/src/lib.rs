@@ -0,0 +1,204 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{Data, DeriveInput, Fields, ItemFn, ReturnType, Type, parse_macro_input, parse_quote};
+
+/// Adds a layer with the formatted context to the error a function returns:
+/// `#[context("loading {}", path)]`.
+///
+/// The message is formatted only when the function fails, after the body has
+/// run, so the body borrows the arguments rather than taking them. If the body
+/// moves an argument the message refers to, write `#[context(eager, ...)]` to
+/// format it before the body runs instead, at the cost of formatting on every
+/// call.
+#[proc_macro_attribute]
+pub fn context(args: TokenStream, item: TokenStream) -> TokenStream {
+    let (eager, args) = eager(args.into());
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+    if args.is_empty() {
+        return syn::Error::new(sig.span(), "expected a context format string")
+            .to_compile_error()
+            .into();
+    }
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return syn::Error::new(
+                sig.span(),
+                "#[context] requires a function returning a Result",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    let mut output = (**output).clone();
+    EraseImplTrait.visit_type_mut(&mut output);
+    let format = quote!(::err_report::__private::format!(#args));
+    let (message, context) = if eager {
+        (
+            quote!(let __err_report_context = #format;),
+            quote!(__err_report_context),
+        )
+    } else {
+        (quote!(), format)
+    };
+    // Eager bodies may move the arguments, lazy ones must leave them for the
+    // message.
+    let capture = if eager { quote!(move) } else { quote!() };
+    let body = if sig.asyncness.is_some() {
+        quote! {
+            let __err_report_result = async #capture {
+                let __err_report_result: #output = #block;
+                __err_report_result
+            }
+            .await;
+        }
+    } else {
+        quote! {
+            #[allow(clippy::redundant_closure_call)]
+            let __err_report_result = (#capture || -> #output #block)();
+        }
+    };
+    // Spanning the call at the function name makes `add_context` record the
+    // function's location rather than the macro's.
+    let add_context = quote_spanned! {sig.ident.span()=>
+        __err_report_result.map_err(|__err_report_error| {
+            __err_report_error.add_context(#context)
+        })
+    };
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #message
+            #body
+            #add_context
+        }
+    }
+    .into()
+}
+
+/// Splits a leading `eager,` off the attribute arguments.
+fn eager(args: proc_macro2::TokenStream) -> (bool, proc_macro2::TokenStream) {
+    let mut tokens = args.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(comma)))
+            if ident == "eager" && comma.as_char() == ',' =>
+        {
+            (true, tokens.collect())
+        }
+        _ => (false, args),
+    }
+}
+
+/// Replaces `impl Trait` with `_`, which closure return types and `let`
+/// annotations accept.
+struct EraseImplTrait;
+
+impl VisitMut for EraseImplTrait {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::ImplTrait(_) = ty {
+            *ty = parse_quote!(_);
+        } else {
+            visit_mut::visit_type_mut(self, ty);
+        }
+    }
+}
+
+#[proc_macro_derive(ReportError)]
+pub fn derive_report_error(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let Data::Enum(data) = &input.data else {
+        return syn::Error::new(input.span(), "ReportError can only be derived for enums")
+            .to_compile_error()
+            .into();
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let constructors = data.variants.iter().filter_map(|variant| {
+        let field = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+            Fields::Named(fields) if fields.named.len() == 1 => &fields.named[0],
+            _ => return None,
+        };
+        let variant_name = &variant.ident;
+        let constructor =
+            constructor_ident(&snake_case(&variant_name.to_string()), variant_name.span());
+        let source = &field.ty;
+        let build = match &field.ident {
+            Some(field_name) => quote!(|source| #name::#variant_name { #field_name: source }),
+            None => quote!(#name::#variant_name),
+        };
+        Some(quote! {
+            #[allow(dead_code)]
+            pub fn #constructor(
+                report: ::err_report::Report<#source>,
+            ) -> ::err_report::Report<Self> {
+                report.map_inner(#build)
+            }
+        })
+    });
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#constructors)*
+        }
+    }
+    .into()
+}
+
+/// `HTTPError` becomes `http_error`: a run of capitals is one word, its last
+/// capital starting the next word when lowercase follows.
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if !previous.is_uppercase() || next_is_lower {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// A constructor named `name`, raw when it is a keyword such as `type`.
+fn constructor_ident(name: &str, span: Span) -> Ident {
+    match name {
+        // Keywords that cannot be raw identifiers.
+        "self" | "super" | "crate" => format_ident!("{}_", name, span = span),
+        _ if syn::parse_str::<Ident>(name).is_err() => Ident::new_raw(name, span),
+        _ => Ident::new(name, span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snake_case_groups_acronyms() {
+        assert_eq!(snake_case("Io"), "io");
+        assert_eq!(snake_case("ParseInt"), "parse_int");
+        assert_eq!(snake_case("HTTPError"), "http_error");
+        assert_eq!(snake_case("ParseURL"), "parse_url");
+        assert_eq!(snake_case("IO"), "io");
+        assert_eq!(snake_case("Utf8Error"), "utf8_error");
+    }
+
+    #[test]
+    fn keywords_become_raw_identifiers() {
+        let span = Span::call_site();
+        assert_eq!(constructor_ident("type", span).to_string(), "r#type");
+        assert_eq!(constructor_ident("self", span).to_string(), "self_");
+        assert_eq!(constructor_ident("io", span).to_string(), "io");
+    }
+}
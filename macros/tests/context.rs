@@ -0,0 +1,120 @@
+use err_report::{Report, StringError, context};
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug)]
+struct Config {
+    name: String,
+}
+
+#[context(eager, "loading {}", path)]
+fn load(path: String) -> Result<Config, Report<StringError>> {
+    if path.is_empty() {
+        return Err(Report::msg("empty path"));
+    }
+    Ok(Config { name: path })
+}
+
+impl Config {
+    #[context("checking {}", self.name)]
+    fn check(&self) -> Result<&str, Report<StringError>> {
+        Ok(&self.name)
+    }
+}
+
+#[context("listing {}", dir)]
+fn list(dir: &str) -> Result<impl Iterator<Item = char> + '_, Report<StringError>> {
+    Ok(dir.chars())
+}
+
+#[context(eager, "reading {}", name)]
+async fn read(name: String) -> Result<Vec<u8>, Report<StringError>> {
+    let config = load(name)?;
+    Ok(config.name.into_bytes())
+}
+
+/// Counts how often it is formatted.
+struct Counted<'a>(&'a Cell<usize>);
+
+impl Display for Counted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        f.write_str("counted")
+    }
+}
+
+#[context("checking {}", Counted(formats))]
+fn counted(formats: &Cell<usize>, fail: bool) -> Result<(), Report<StringError>> {
+    if fail {
+        return Err(Report::msg("failed"));
+    }
+    Ok(())
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = std::task::Waker::noop();
+    match future
+        .as_mut()
+        .poll(&mut std::task::Context::from_waker(waker))
+    {
+        std::task::Poll::Ready(output) => output,
+        std::task::Poll::Pending => unreachable!("no awaits pend"),
+    }
+}
+
+#[test]
+fn eager_context_is_formatted_before_the_body_moves_arguments() {
+    assert_eq!(
+        load("a".into()).ok().map(|config| config.name).as_deref(),
+        Some("a")
+    );
+    let report = load(String::new()).expect_err("empty path fails");
+    assert_eq!(
+        report
+            .layers()
+            .last()
+            .and_then(|layer| layer.context_str())
+            .as_deref(),
+        Some("loading ")
+    );
+}
+
+#[test]
+fn lazy_context_is_formatted_only_on_failure() {
+    let formats = Cell::new(0);
+    assert!(counted(&formats, false).is_ok());
+    assert_eq!(formats.get(), 0);
+    let report = counted(&formats, true).expect_err("fails");
+    assert_eq!(formats.get(), 1);
+    assert_eq!(
+        report
+            .layers()
+            .last()
+            .and_then(|layer| layer.context_str())
+            .as_deref(),
+        Some("checking counted")
+    );
+}
+
+#[test]
+fn methods_borrowing_self() {
+    let config = Config { name: "a".into() };
+    assert_eq!(config.check().ok(), Some("a"));
+}
+
+#[test]
+fn impl_trait_in_the_ok_type() {
+    assert_eq!(list("ab").ok().map(Iterator::count), Some(2));
+}
+
+#[test]
+fn async_fns() {
+    assert_eq!(block_on(read("ab".into())).ok(), Some(b"ab".to_vec()));
+    let report = block_on(read(String::new())).expect_err("empty path fails");
+    let contexts = report
+        .frames()
+        .filter_map(|layer| layer.context_str())
+        .collect::<Vec<_>>();
+    assert_eq!(contexts, ["reading ", "loading "]);
+}
@@ -0,0 +1,19 @@
+use err_report::{Report, ReportError, StringError};
+
+#[derive(ReportError)]
+enum AppError {
+    HTTPError(StringError),
+    Type { source: StringError },
+}
+
+#[test]
+fn constructors_are_snake_case() {
+    let report = AppError::http_error(Report::msg("timeout"));
+    assert!(matches!(report.inner(), AppError::HTTPError(error) if error.as_str() == "timeout"));
+}
+
+#[test]
+fn keyword_variants_get_raw_constructors() {
+    let report = AppError::r#type(Report::msg("bad type"));
+    assert!(matches!(report.inner(), AppError::Type { source } if source.as_str() == "bad type"));
+}
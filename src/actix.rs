@@ -0,0 +1,27 @@
+use crate::{AsDynError, Report, config};
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use std::fmt::{Debug, Display};
+
+impl<E> ResponseError for Report<E>
+where
+    E: AsDynError + Debug + Display + ?Sized,
+{
+    fn status_code(&self) -> StatusCode {
+        self.status()
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        self.finalize();
+        let body = if config::expose_details() {
+            self.to_string()
+        } else {
+            self.inner.to_string()
+        };
+        HttpResponse::build(self.status_code())
+            .content_type("text/plain; charset=utf-8")
+            .body(body)
+    }
+}
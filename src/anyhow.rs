@@ -0,0 +1,27 @@
+use crate::{AnyError, Report, Trace, caller};
+use std::backtrace::BacktraceStatus;
+use std::error::Error;
+
+impl From<anyhow::Error> for Report<AnyError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from(error: anyhow::Error) -> Self {
+        if error.backtrace().status() != BacktraceStatus::Captured {
+            return Report::from_boxed(error.reallocate_into_boxed_dyn_error_without_backtrace());
+        }
+        let backtrace = Trace::Rendered(error.backtrace().to_string());
+        Report::from_boxed_with_trace(
+            error.reallocate_into_boxed_dyn_error_without_backtrace(),
+            Some(Box::new(backtrace)),
+            caller(),
+        )
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Error + Send + Sync + 'static + ?Sized,
+{
+    pub fn into_anyhow(self) -> anyhow::Error {
+        anyhow::Error::new(self)
+    }
+}
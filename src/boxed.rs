@@ -0,0 +1,70 @@
+use crate::{AnyError, IntoAnyError, Report};
+use alloc::boxed::Box;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+
+/// A report of any error type behind one concrete type, so it survives a
+/// trip through `Box<dyn Error + Send + Sync>` and can be downcast back with
+/// [`BoxedReport::recover`].
+pub struct BoxedReport {
+    report: Report<AnyError>,
+}
+
+impl BoxedReport {
+    pub fn into_report(self) -> Report<AnyError> {
+        self.report
+    }
+
+    /// The report inside `error` if it is a `BoxedReport`, or `error` back.
+    pub fn recover(error: Box<AnyError>) -> Result<Report<AnyError>, Box<AnyError>> {
+        error.downcast::<Self>().map(|boxed| boxed.report)
+    }
+}
+
+impl Deref for BoxedReport {
+    type Target = Report<AnyError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.report
+    }
+}
+
+impl Debug for BoxedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.report, f)
+    }
+}
+
+impl Display for BoxedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.report, f)
+    }
+}
+
+impl Error for BoxedReport {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.report.source()
+    }
+}
+
+impl<E> From<Report<E>> for BoxedReport
+where
+    E: IntoAnyError + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        Self {
+            report: report.into_any(),
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: IntoAnyError + ?Sized,
+{
+    /// The report as a [`BoxedReport`] behind `Box<dyn Error + Send + Sync>`.
+    pub fn into_boxed_error(self) -> Box<AnyError> {
+        Box::new(BoxedReport::from(self))
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{AnyError, Report};
+use alloc::boxed::Box;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+
+pub trait AsDynError {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static);
+}
+
+impl<E> AsDynError for E
+where
+    E: Error + 'static,
+{
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+impl AsDynError for AnyError {
+    fn as_dyn_error(&self) -> &(dyn Error + 'static) {
+        self
+    }
+}
+
+pub trait IntoAnyError: AsDynError + Send + Sync + 'static {
+    fn into_any_error(self: Box<Self>) -> Box<AnyError>;
+}
+
+impl<E> IntoAnyError for E
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn into_any_error(self: Box<Self>) -> Box<AnyError> {
+        self
+    }
+}
+
+impl IntoAnyError for AnyError {
+    fn into_any_error(self: Box<Self>) -> Box<AnyError> {
+        self
+    }
+}
+
+/// What follows the inner error in a report's chain, in place of its own
+/// source.
+pub(crate) struct Cause {
+    pub(crate) error: Box<AnyError>,
+    /// Set by [`Report::set_source`] on a report without a cause, so the
+    /// inner error's own source still follows `error` and its chain.
+    pub(crate) then_inner: bool,
+}
+
+impl Cause {
+    pub(crate) fn new(error: Box<AnyError>) -> Self {
+        Self {
+            error,
+            then_inner: false,
+        }
+    }
+}
+
+pub(crate) struct Caused {
+    error: Box<AnyError>,
+    cause: Box<AnyError>,
+}
+
+impl Caused {
+    pub(crate) fn new(error: Box<AnyError>, cause: Box<AnyError>) -> Self {
+        Self { error, cause }
+    }
+}
+
+impl Debug for Caused {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.error, f)
+    }
+}
+
+impl Display for Caused {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for Caused {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.cause.as_ref())
+    }
+}
+
+/// A report carried as the source of another by [`Report::wrap_err`]. It
+/// renders on one line, inner message and layers, whatever the formatter
+/// flags, so `{:#}` on a chain does not nest pretty reports.
+pub(crate) struct Wrapped<E>(pub(crate) Report<E>)
+where
+    E: ?Sized;
+
+impl<E> Debug for Wrapped<E>
+where
+    E: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl<E> Display for Wrapped<E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<E> Error for Wrapped<E>
+where
+    E: Error + ?Sized,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "error-stack")]
+pub(crate) struct MessageChain {
+    message: String,
+    source: Option<Box<MessageChain>>,
+}
+
+#[cfg(feature = "error-stack")]
+impl MessageChain {
+    pub(crate) fn new(message: String, source: Option<Box<MessageChain>>) -> Self {
+        Self { message, source }
+    }
+}
+
+#[cfg(feature = "error-stack")]
+impl Debug for MessageChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.message, f)
+    }
+}
+
+#[cfg(feature = "error-stack")]
+impl Display for MessageChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "error-stack")]
+impl Error for MessageChain {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+#[derive(Clone)]
+pub struct Chain<'a> {
+    head: Option<&'a (dyn Error + 'static)>,
+    next: Option<&'a (dyn Error + 'static)>,
+    /// Walked once `next` runs out.
+    tail: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> Chain<'a> {
+    pub(crate) fn new(
+        head: Option<&'a (dyn Error + 'static)>,
+        source: Option<&'a (dyn Error + 'static)>,
+        tail: Option<&'a (dyn Error + 'static)>,
+    ) -> Self {
+        Self {
+            head,
+            next: source,
+            tail,
+        }
+    }
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(head) = self.head.take() {
+            return Some(head);
+        }
+        let current = match self.next {
+            Some(current) => current,
+            None => self.tail.take()?,
+        };
+        self.next = current.source();
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Report, StringError};
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+    use core::error::Error;
+    use core::fmt::{self, Display, Formatter};
+
+    #[derive(Debug)]
+    struct Outer(StringError);
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    fn messages(report: &Report<Outer>) -> Vec<String> {
+        report.chain().map(|error| error.to_string()).collect()
+    }
+
+    #[test]
+    fn set_source_keeps_the_inner_source() {
+        let mut report = Report::new(Outer(StringError::new("disk")));
+        report.set_source(StringError::new("extra"));
+        assert_eq!(messages(&report), ["outer", "extra", "disk"]);
+        report.set_source(StringError::new("first"));
+        assert_eq!(messages(&report), ["outer", "first", "extra", "disk"]);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::Report;
+use crate::sync::RwLock;
+use alloc::collections::BTreeMap;
+use core::fmt::{Display, Formatter};
+
+static EXPLANATIONS: RwLock<BTreeMap<&'static str, &'static str>> = RwLock::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ErrorCode(pub &'static str);
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+pub fn register(code: &'static str, explanation: &'static str) {
+    EXPLANATIONS.write().insert(code, explanation);
+}
+
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS.read().get(code).copied()
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_code(self, code: &'static str) -> Report<E> {
+        self.attach(ErrorCode(code))
+    }
+
+    pub fn code(&self) -> Option<&'static str> {
+        self.request_ref::<ErrorCode>().map(|code| code.0)
+    }
+
+    pub fn explanation(&self) -> Option<&'static str> {
+        explain(self.code()?)
+    }
+}
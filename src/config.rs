@@ -0,0 +1,552 @@
+use crate::sync::RwLock;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "color")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color when exiting through `Termination` to a terminal stderr, unless
+    /// `NO_COLOR` is set; `Display` elsewhere stays plain.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[cfg(feature = "color")]
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(0);
+
+#[cfg(feature = "color")]
+pub fn set_color_choice(choice: ColorChoice) {
+    let value = match choice {
+        ColorChoice::Auto => 0,
+        ColorChoice::Always => 1,
+        ColorChoice::Never => 2,
+    };
+    COLOR_CHOICE.store(value, Ordering::Relaxed);
+}
+
+#[cfg(feature = "color")]
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+#[cfg(feature = "color")]
+pub(crate) fn use_color(config: &Config) -> bool {
+    use std::io::IsTerminal;
+
+    match config.color_choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            config.to_stderr
+                && std::env::var_os("NO_COLOR").is_none()
+                && std::io::stderr().is_terminal()
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+pub fn set_tracing_level(level: tracing::Level) {
+    crate::instrument::set_level(level);
+}
+
+#[cfg(feature = "tracing")]
+pub fn tracing_level() -> tracing::Level {
+    crate::instrument::level()
+}
+
+static EXPOSE_DETAILS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_expose_details(expose: bool) {
+    EXPOSE_DETAILS.store(expose, Ordering::Relaxed);
+}
+
+pub fn expose_details() -> bool {
+    EXPOSE_DETAILS.load(Ordering::Relaxed)
+}
+
+static STABLE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Renders line and column numbers as `LL:CC` and absolute directories as
+/// `$DIR`, and leaves out timestamps, error IDs, thread and system details
+/// and traces, so snapshots of the output only change when the report does.
+pub fn set_stable_output(stable: bool) {
+    STABLE_OUTPUT.store(stable, Ordering::Relaxed);
+}
+
+pub fn stable_output() -> bool {
+    STABLE_OUTPUT.load(Ordering::Relaxed)
+}
+
+static DEDUP_LAYERS: AtomicBool = AtomicBool::new(false);
+
+/// Merges a new layer into the previous one when both have the same
+/// location and context, e.g. in retry loops, counting the repeats instead
+/// of storing each one. Rendering collapses such runs either way.
+pub fn set_dedup_layers(dedup: bool) {
+    DEDUP_LAYERS.store(dedup, Ordering::Relaxed);
+}
+
+pub fn dedup_layers() -> bool {
+    DEDUP_LAYERS.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContextMode {
+    #[default]
+    Overwrite,
+    Append,
+}
+
+static APPEND_CONTEXT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_context_mode(mode: ContextMode) {
+    APPEND_CONTEXT.store(mode == ContextMode::Append, Ordering::Relaxed);
+}
+
+pub fn context_mode() -> ContextMode {
+    if APPEND_CONTEXT.load(Ordering::Relaxed) {
+        ContextMode::Append
+    } else {
+        ContextMode::Overwrite
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedactionMode {
+    #[default]
+    Redact,
+    Reveal,
+}
+
+static REVEAL_REDACTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether [`Redacted`](crate::Redacted) values render as `[REDACTED]` or
+/// as themselves. Only reveal them in debugging builds.
+pub fn set_redaction_mode(mode: RedactionMode) {
+    REVEAL_REDACTED.store(mode == RedactionMode::Reveal, Ordering::Relaxed);
+}
+
+pub fn redaction_mode() -> RedactionMode {
+    if REVEAL_REDACTED.load(Ordering::Relaxed) {
+        RedactionMode::Reveal
+    } else {
+        RedactionMode::Redact
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    #[default]
+    Normal,
+    Compact,
+    Full,
+    Json,
+}
+
+#[cfg(feature = "std")]
+impl Verbosity {
+    fn from_env(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "normal" | "default" => Some(Verbosity::Normal),
+            "compact" => Some(Verbosity::Compact),
+            "full" => Some(Verbosity::Full),
+            "json" => Some(Verbosity::Json),
+            _ => None,
+        }
+    }
+}
+
+const VERBOSITY_UNSET: u8 = u8::MAX;
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(VERBOSITY_UNSET);
+
+#[cfg(feature = "std")]
+fn env_verbosity() -> Verbosity {
+    static ENV: OnceLock<Verbosity> = OnceLock::new();
+    *ENV.get_or_init(|| {
+        std::env::var("ERR_REPORT")
+            .ok()
+            .and_then(|value| Verbosity::from_env(&value))
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn env_verbosity() -> Verbosity {
+    Verbosity::default()
+}
+
+fn verbosity_override() -> Option<Verbosity> {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Some(Verbosity::Normal),
+        1 => Some(Verbosity::Compact),
+        2 => Some(Verbosity::Full),
+        3 => Some(Verbosity::Json),
+        _ => None,
+    }
+}
+
+fn store_verbosity(verbosity: Option<Verbosity>) {
+    let value = match verbosity {
+        Some(Verbosity::Normal) => 0,
+        Some(Verbosity::Compact) => 1,
+        Some(Verbosity::Full) => 2,
+        Some(Verbosity::Json) => 3,
+        None => VERBOSITY_UNSET,
+    };
+    VERBOSITY.store(value, Ordering::Relaxed);
+}
+
+/// The explicitly set verbosity, or the `ERR_REPORT` environment variable
+/// (read once) when none was set.
+pub fn verbosity() -> Verbosity {
+    verbosity_override().unwrap_or_else(env_verbosity)
+}
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    store_verbosity(Some(verbosity));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LocationStyle {
+    #[default]
+    Full,
+    FileNameOnly,
+    /// Relative to the current working directory, usually the workspace root.
+    Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    NewestFirst,
+    RootFirst,
+}
+
+static LOCATION_STYLE: AtomicU8 = AtomicU8::new(0);
+// Shared so `Config::current` on every render does not copy them.
+static STRIP_PREFIX: RwLock<Option<Arc<str>>> = RwLock::new(None);
+static SEPARATOR: RwLock<Option<Arc<str>>> = RwLock::new(None);
+static ROOT_FIRST: AtomicBool = AtomicBool::new(false);
+static MAX_LAYERS_SHOWN: AtomicUsize = AtomicUsize::new(usize::MAX);
+static MAX_LAYERS: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// The smallest [`ConfigBuilder::max_layers`]: room for the origin, the
+/// newest layer and two more, so there is always a run of scope layers or of
+/// layers in between to fold.
+const MIN_MAX_LAYERS: usize = 4;
+
+/// The installed [`Config::max_layers`].
+pub(crate) fn layer_cap() -> usize {
+    MAX_LAYERS.load(Ordering::Relaxed)
+}
+
+pub fn set_order(order: Order) {
+    ROOT_FIRST.store(order == Order::RootFirst, Ordering::Relaxed);
+}
+
+pub fn order() -> Order {
+    if ROOT_FIRST.load(Ordering::Relaxed) {
+        Order::RootFirst
+    } else {
+        Order::NewestFirst
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    location_style: LocationStyle,
+    strip_prefix: Option<Arc<str>>,
+    separator: Option<Arc<str>>,
+    order: Order,
+    max_layers_shown: Option<usize>,
+    max_layers: Option<usize>,
+    verbosity: Option<Verbosity>,
+    expose_details: bool,
+    stable_output: bool,
+    dedup_layers: bool,
+    context_mode: ContextMode,
+    redaction_mode: RedactionMode,
+    #[cfg(feature = "color")]
+    color_choice: ColorChoice,
+    /// Set when rendering straight to stderr, the one writer `Auto` checks.
+    #[cfg(feature = "color")]
+    to_stderr: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            location_style: LocationStyle::Full,
+            strip_prefix: None,
+            separator: None,
+            order: Order::NewestFirst,
+            max_layers_shown: None,
+            max_layers: None,
+            verbosity: None,
+            expose_details: false,
+            stable_output: false,
+            dedup_layers: false,
+            context_mode: ContextMode::Overwrite,
+            redaction_mode: RedactionMode::Redact,
+            #[cfg(feature = "color")]
+            color_choice: ColorChoice::Auto,
+            #[cfg(feature = "color")]
+            to_stderr: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder {
+            config: Config::default(),
+        }
+    }
+
+    pub fn current() -> Self {
+        let max_layers_shown = MAX_LAYERS_SHOWN.load(Ordering::Relaxed);
+        let max_layers = MAX_LAYERS.load(Ordering::Relaxed);
+        Self {
+            location_style: match LOCATION_STYLE.load(Ordering::Relaxed) {
+                1 => LocationStyle::FileNameOnly,
+                2 => LocationStyle::Relative,
+                _ => LocationStyle::Full,
+            },
+            strip_prefix: STRIP_PREFIX.read().clone(),
+            separator: SEPARATOR.read().clone(),
+            order: order(),
+            max_layers_shown: (max_layers_shown != usize::MAX).then_some(max_layers_shown),
+            max_layers: (max_layers != usize::MAX).then_some(max_layers),
+            verbosity: verbosity_override(),
+            expose_details: expose_details(),
+            stable_output: stable_output(),
+            dedup_layers: dedup_layers(),
+            context_mode: context_mode(),
+            redaction_mode: redaction_mode(),
+            #[cfg(feature = "color")]
+            color_choice: color_choice(),
+            #[cfg(feature = "color")]
+            to_stderr: false,
+        }
+    }
+
+    pub fn install(self) {
+        let location_style = match self.location_style {
+            LocationStyle::Full => 0,
+            LocationStyle::FileNameOnly => 1,
+            LocationStyle::Relative => 2,
+        };
+        LOCATION_STYLE.store(location_style, Ordering::Relaxed);
+        *STRIP_PREFIX.write() = self.strip_prefix;
+        *SEPARATOR.write() = self.separator;
+        set_order(self.order);
+        MAX_LAYERS_SHOWN.store(
+            self.max_layers_shown.unwrap_or(usize::MAX),
+            Ordering::Relaxed,
+        );
+        MAX_LAYERS.store(self.max_layers.unwrap_or(usize::MAX), Ordering::Relaxed);
+        store_verbosity(self.verbosity);
+        set_expose_details(self.expose_details);
+        set_stable_output(self.stable_output);
+        set_dedup_layers(self.dedup_layers);
+        set_context_mode(self.context_mode);
+        set_redaction_mode(self.redaction_mode);
+        #[cfg(feature = "color")]
+        set_color_choice(self.color_choice);
+    }
+
+    pub(crate) fn with_order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub(crate) fn with_stable_output(mut self) -> Self {
+        self.stable_output = true;
+        self
+    }
+
+    pub(crate) fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    pub(crate) fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = Some(verbosity);
+        self
+    }
+
+    #[cfg(feature = "color")]
+    pub(crate) fn with_stderr(mut self) -> Self {
+        self.to_stderr = true;
+        self
+    }
+
+    pub fn location_style(&self) -> LocationStyle {
+        self.location_style
+    }
+
+    pub fn strip_prefix(&self) -> Option<&str> {
+        self.strip_prefix.as_deref()
+    }
+
+    /// What joins layers in the single-line format, `", "` by default.
+    pub fn separator(&self) -> &str {
+        self.separator.as_deref().unwrap_or(", ")
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    pub fn max_layers_shown(&self) -> Option<usize> {
+        self.max_layers_shown
+    }
+
+    pub fn max_layers(&self) -> Option<usize> {
+        self.max_layers
+    }
+
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity.unwrap_or_else(env_verbosity)
+    }
+
+    pub fn expose_details(&self) -> bool {
+        self.expose_details
+    }
+
+    pub fn stable_output(&self) -> bool {
+        self.stable_output
+    }
+
+    pub fn dedup_layers(&self) -> bool {
+        self.dedup_layers
+    }
+
+    pub fn context_mode(&self) -> ContextMode {
+        self.context_mode
+    }
+
+    pub fn redaction_mode(&self) -> RedactionMode {
+        self.redaction_mode
+    }
+
+    #[cfg(feature = "color")]
+    pub fn color_choice(&self) -> ColorChoice {
+        self.color_choice
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn location_style(mut self, style: LocationStyle) -> Self {
+        self.config.location_style = style;
+        self
+    }
+
+    /// Removes `prefix` from the start of every location's file path, e.g.
+    /// `/home/ci/build/`, before the location style is applied.
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.strip_prefix = Some(prefix.into().into());
+        self
+    }
+
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.config.separator = Some(separator.into().into());
+        self
+    }
+
+    pub fn order(mut self, order: Order) -> Self {
+        self.config.order = order;
+        self
+    }
+
+    pub fn max_layers_shown(mut self, max: usize) -> Self {
+        self.config.max_layers_shown = Some(max);
+        self
+    }
+
+    /// Caps how many layers a report stores. Past the cap, the oldest layers
+    /// after the origin are replaced by a `… N layers omitted …` marker that
+    /// keeps the newest attachment of each type among them.
+    ///
+    /// The cap is at least 4, so the origin, the newest layer and a run to
+    /// fold always fit: smaller values are raised to 4, and
+    /// [`Config::max_layers`] reports the raised value.
+    pub fn max_layers(mut self, max: usize) -> Self {
+        self.config.max_layers = Some(max.max(MIN_MAX_LAYERS));
+        self
+    }
+
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.config.verbosity = Some(verbosity);
+        self
+    }
+
+    pub fn expose_details(mut self, expose: bool) -> Self {
+        self.config.expose_details = expose;
+        self
+    }
+
+    pub fn stable_output(mut self, stable: bool) -> Self {
+        self.config.stable_output = stable;
+        self
+    }
+
+    pub fn dedup_layers(mut self, dedup: bool) -> Self {
+        self.config.dedup_layers = dedup;
+        self
+    }
+
+    pub fn context_mode(mut self, mode: ContextMode) -> Self {
+        self.config.context_mode = mode;
+        self
+    }
+
+    pub fn redaction_mode(mut self, mode: RedactionMode) -> Self {
+        self.config.redaction_mode = mode;
+        self
+    }
+
+    #[cfg(feature = "color")]
+    pub fn color_choice(mut self, choice: ColorChoice) -> Self {
+        self.config.color_choice = choice;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+
+    pub fn install(self) {
+        self.config.install();
+    }
+}
+
+#[cfg(all(test, feature = "color"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_color_only_considers_stderr() {
+        let auto = Config::builder().color_choice(ColorChoice::Auto).build();
+        assert!(!use_color(&auto));
+        let always = Config::builder().color_choice(ColorChoice::Always).build();
+        assert!(use_color(&always));
+        let never = Config::builder().color_choice(ColorChoice::Never).build();
+        assert!(!use_color(&never.with_stderr()));
+    }
+}
@@ -0,0 +1,143 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+
+type DynDisplay = dyn Display + Send + Sync + 'static;
+
+/// The context attached to a [`Layer`](crate::Layer). String literals and
+/// already formatted `String`s are stored without boxing.
+pub struct ContextValue(Repr);
+
+enum Repr {
+    Static(&'static str),
+    Owned(String),
+    #[cfg(feature = "std")]
+    Shared(Arc<DynDisplay>),
+    Boxed(Box<DynDisplay>),
+}
+
+impl ContextValue {
+    pub fn new<Ctx>(context: Ctx) -> Self
+    where
+        Ctx: Display + Send + Sync + 'static,
+    {
+        let mut slot = Some(context);
+        let any = &mut slot as &mut dyn Any;
+        if let Some(literal) = any.downcast_mut::<Option<&'static str>>() {
+            return Self(Repr::Static(literal.take().unwrap_or_default()));
+        }
+        if let Some(owned) = any.downcast_mut::<Option<String>>() {
+            return Self(Repr::Owned(owned.take().unwrap_or_default()));
+        }
+        Self(Repr::Boxed(Box::new(
+            slot.expect("context is only taken on a downcast hit"),
+        )))
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn shared(context: Arc<DynDisplay>) -> Self {
+        Self(Repr::Shared(context))
+    }
+
+    /// Whether the two render the same text. Compares string slices and
+    /// shared values directly; only two distinct formatted values need one
+    /// of them written out.
+    pub(crate) fn renders_same(&self, other: &ContextValue) -> bool {
+        match (self.as_str(), other.as_str()) {
+            (Some(this), Some(other)) => this == other,
+            (Some(text), None) => writes(other, text),
+            (None, Some(text)) => writes(self, text),
+            #[cfg(feature = "std")]
+            (None, None) if self.is_shared_with(other) => true,
+            (None, None) => writes(other, &self.to_string()),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn is_shared_with(&self, other: &ContextValue) -> bool {
+        match (&self.0, &other.0) {
+            (Repr::Shared(this), Repr::Shared(other)) => Arc::ptr_eq(this, other),
+            _ => false,
+        }
+    }
+
+    /// Returns the context as a string slice if it was given as one.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.0 {
+            Repr::Static(literal) => Some(literal),
+            Repr::Owned(owned) => Some(owned),
+            #[cfg(feature = "std")]
+            Repr::Shared(_) => None,
+            Repr::Boxed(_) => None,
+        }
+    }
+}
+
+/// A writer that checks what it is given against the text still expected.
+struct Matches<'a> {
+    rest: &'a str,
+}
+
+impl core::fmt::Write for Matches<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.rest = self.rest.strip_prefix(s).ok_or(core::fmt::Error)?;
+        Ok(())
+    }
+}
+
+/// Whether `value` displays as exactly `expected`, without formatting it
+/// into a buffer.
+fn writes(value: &DynDisplay, expected: &str) -> bool {
+    use core::fmt::Write;
+
+    let mut matches = Matches { rest: expected };
+    write!(matches, "{}", value).is_ok() && matches.rest.is_empty()
+}
+
+impl Deref for ContextValue {
+    type Target = DynDisplay;
+
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            Repr::Static(literal) => literal,
+            Repr::Owned(owned) => owned,
+            #[cfg(feature = "std")]
+            Repr::Shared(shared) => &**shared,
+            Repr::Boxed(boxed) => &**boxed,
+        }
+    }
+}
+
+impl Display for ContextValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl Debug for ContextValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.to_string(), f)
+    }
+}
+
+impl From<&'static str> for ContextValue {
+    fn from(literal: &'static str) -> Self {
+        Self(Repr::Static(literal))
+    }
+}
+
+impl From<String> for ContextValue {
+    fn from(owned: String) -> Self {
+        Self(Repr::Owned(owned))
+    }
+}
+
+impl From<Box<DynDisplay>> for ContextValue {
+    fn from(boxed: Box<DynDisplay>) -> Self {
+        Self(Repr::Boxed(boxed))
+    }
+}
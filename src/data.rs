@@ -0,0 +1,91 @@
+use crate::{AsDynError, Layer, Report};
+#[cfg(feature = "serde")]
+use crate::{Config, render};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::panic::Location;
+
+/// An owned copy of a [`Layer`], with the context rendered to a string. It
+/// is also how layers travel in a serialized report, where `file` is
+/// shortened per [`Config::location_style`](crate::Config::location_style)
+/// and blank with [`Config::stable_output`](crate::Config::stable_output).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerData {
+    pub context: Option<String>,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl LayerData {
+    /// The copy of `layer` that rendering with `config` would show.
+    #[cfg(feature = "serde")]
+    pub(crate) fn rendered(layer: &Layer, config: &Config) -> Self {
+        let location = layer
+            .captured_location()
+            .filter(|_| !config.stable_output());
+        Self {
+            context: layer.context_str(),
+            file: location
+                .map(|location| render::file(location, config))
+                .unwrap_or_default()
+                .to_string(),
+            line: location.map_or(0, Location::line),
+            column: location.map_or(0, Location::column),
+        }
+    }
+}
+
+/// Copies the location as captured.
+impl From<&Layer> for LayerData {
+    fn from(layer: &Layer) -> Self {
+        let location = layer.captured_location();
+        Self {
+            context: layer.context_str(),
+            file: location.map_or("", Location::file).to_string(),
+            line: location.map_or(0, Location::line),
+            column: location.map_or(0, Location::column),
+        }
+    }
+}
+
+impl Display for LayerData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match &self.context {
+            Some(context) => write!(
+                f,
+                "{} @ {}:{}:{}",
+                context, self.file, self.line, self.column
+            ),
+            None => write!(f, "@ {}:{}:{}", self.file, self.line, self.column),
+        }
+    }
+}
+
+/// A report as plain owned data, from [`Report::to_data`]. Layers are
+/// origin first, like [`Report::layers`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportData {
+    pub message: String,
+    pub layers: Vec<LayerData>,
+    pub sources: Vec<String>,
+    pub code: Option<String>,
+}
+
+impl<E> Report<E>
+where
+    E: AsDynError + ?Sized,
+{
+    pub fn to_data(&self) -> ReportData {
+        let mut chain = self.chain().map(|e| e.to_string());
+        ReportData {
+            message: chain.next().unwrap_or_default(),
+            layers: self.layers.iter().map(LayerData::from).collect(),
+            sources: chain.collect(),
+            code: self.code().map(str::to_string),
+        }
+    }
+}
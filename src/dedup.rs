@@ -0,0 +1,63 @@
+use crate::{Layer, Report, config};
+use alloc::boxed::Box;
+
+/// How many consecutive identical layers were merged into the one carrying it.
+pub(crate) struct Repeated(usize);
+
+impl Layer {
+    /// How many consecutive identical layers this one stands for: 1 unless
+    /// [`config::set_dedup_layers`] merged repeats into it.
+    pub fn repeats(&self) -> usize {
+        self.attachments
+            .iter()
+            .find_map(|attachment| attachment.downcast_ref::<Repeated>())
+            .map_or(1, |repeated| repeated.0)
+    }
+
+    /// Same location and context, so the two render the same.
+    pub(crate) fn same_as(&self, other: &Layer) -> bool {
+        self.location == other.location
+            && self.omitted().is_none()
+            && other.omitted().is_none()
+            && match (&self.context, &other.context) {
+                (Some(this), Some(other)) => this.renders_same(other),
+                (this, other) => this.is_none() && other.is_none(),
+            }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// With [`config::dedup_layers`], merges the newest layer into the one
+    /// before it when they are the same, keeping the older timestamp.
+    pub(crate) fn merge_repeated(&mut self) {
+        let len = self.layers.len();
+        if !config::dedup_layers()
+            || len < 2
+            || !self.layers[len - 1].same_as(&self.layers[len - 2])
+        {
+            return;
+        }
+        let layer = self.layers.pop().expect("checked above");
+        let previous = self.layers.last_mut().expect("checked above");
+        let repeats = previous.repeats() + layer.repeats();
+        previous
+            .attachments
+            .retain(|attachment| !attachment.is::<Repeated>());
+        #[cfg(feature = "timestamps")]
+        let attachments = layer
+            .attachments
+            .into_iter()
+            .filter(|attachment| !attachment.is::<crate::Timestamp>());
+        #[cfg(not(feature = "timestamps"))]
+        let attachments = layer.attachments;
+        previous.attachments.extend(
+            attachments
+                .into_iter()
+                .filter(|attachment| !attachment.is::<Repeated>()),
+        );
+        previous.attachments.push(Box::new(Repeated(repeats)));
+    }
+}
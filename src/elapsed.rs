@@ -0,0 +1,52 @@
+use crate::Report;
+#[cfg(feature = "std")]
+use crate::{ContextValue, Layer, caller};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::fmt::Display;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// How long the failed operation ran, attached by [`Report::with_elapsed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Elapsed(pub Duration);
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_elapsed(self, elapsed: Duration) -> Report<E> {
+        self.attach(Elapsed(elapsed))
+    }
+
+    /// The most recent duration given to [`Report::with_elapsed`].
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.request_ref::<Elapsed>().map(|elapsed| elapsed.0)
+    }
+}
+
+/// Runs `operation` and, if it fails, adds a layer with `context` and how
+/// long the operation took, e.g. `fetching page (after 1.2s)`, also attached
+/// as [`Elapsed`].
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn timed_context<T, E, Ctx, F>(context: Ctx, operation: F) -> Result<T, Report<E>>
+where
+    E: ?Sized,
+    Ctx: Display,
+    F: FnOnce() -> Result<T, Report<E>>,
+{
+    let location = caller();
+    let start = Instant::now();
+    operation().map_err(|mut report| {
+        let elapsed = start.elapsed();
+        let mut layer = Layer::new(location);
+        layer.context = Some(ContextValue::from(format!(
+            "{} (after {:?})",
+            context, elapsed
+        )));
+        layer.attachments.push(Box::new(Elapsed(elapsed)));
+        report.push_layer(layer);
+        report
+    })
+}
@@ -0,0 +1,82 @@
+use crate::chain::MessageChain;
+use crate::{AnyError, Layer, LayerStack, Report};
+use error_stack::{AttachmentKind, FrameKind};
+use std::collections::HashSet;
+use std::error::Error;
+use std::panic::Location;
+use std::sync::{LazyLock, Mutex};
+
+static LOCATIONS: LazyLock<Mutex<HashSet<&'static Location<'static>>>> =
+    LazyLock::new(Default::default);
+
+fn intern(location: &Location<'static>) -> &'static Location<'static> {
+    let mut locations = LOCATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    match locations.get(location) {
+        Some(location) => location,
+        None => {
+            let location: &'static Location<'static> = Box::leak(Box::new(*location));
+            locations.insert(location);
+            location
+        }
+    }
+}
+
+impl<C> From<error_stack::Report<C>> for Report<AnyError>
+where
+    C: ?Sized,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from(report: error_stack::Report<C>) -> Self {
+        let mut contexts = Vec::new();
+        let mut layers = Vec::new();
+        let mut pending = Vec::new();
+        for frame in report.frames() {
+            match frame.kind() {
+                FrameKind::Context(context) => contexts.push(context.to_string()),
+                FrameKind::Attachment(AttachmentKind::Printable(attachment)) => {
+                    pending.push(attachment.to_string());
+                }
+                FrameKind::Attachment(_) => {
+                    if let Some(location) = frame.downcast_ref::<Location<'static>>() {
+                        let mut layer = Layer::new(intern(location));
+                        if !pending.is_empty() {
+                            layer.context = Some(pending.join("; ").into());
+                            pending.clear();
+                        }
+                        layers.push(layer);
+                    }
+                }
+            }
+        }
+        let inner = contexts.into_iter().rev().fold(None, |source, message| {
+            Some(Box::new(MessageChain::new(message, source)))
+        });
+        let inner: Box<AnyError> = match inner {
+            Some(inner) => inner,
+            None => Box::new(MessageChain::new(report.to_string(), None)),
+        };
+        let mut converted = Report::from_boxed(inner);
+        let origin = converted
+            .layers
+            .last_mut()
+            .expect("Report objects must have at least one layer");
+        if !pending.is_empty() {
+            origin.context = Some(pending.join("; ").into());
+        }
+        // error-stack walks frames newest first; ours are stored oldest first.
+        let mut stack = layers.into_iter().rev().collect::<LayerStack>();
+        stack.append(&mut converted.layers);
+        converted.layers = stack;
+        converted
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Error + Send + Sync + 'static + ?Sized,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn into_error_stack(self) -> error_stack::Report<Self> {
+        error_stack::Report::new(self)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::hook::DisplayStderr;
+use crate::{AnyError, Report};
+use std::fmt::Display;
+use std::process::{ExitCode, Termination};
+
+pub struct MainResult<E = AnyError>(Result<(), Report<E>>)
+where
+    E: ?Sized;
+
+impl<E> From<Result<(), Report<E>>> for MainResult<E>
+where
+    E: ?Sized,
+{
+    fn from(value: Result<(), Report<E>>) -> Self {
+        Self(value)
+    }
+}
+
+impl<E> Termination for MainResult<E>
+where
+    E: Display + ?Sized,
+{
+    fn report(self) -> ExitCode {
+        match self.0 {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(report) => report.report(),
+        }
+    }
+}
+
+impl<E> Termination for Report<E>
+where
+    E: Display + ?Sized,
+{
+    fn report(self) -> ExitCode {
+        self.finalize();
+        eprintln!("Error: {:#}", DisplayStderr(&self));
+        self.request_ref::<ExitCode>()
+            .copied()
+            .unwrap_or(ExitCode::FAILURE)
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_exit_code(self, code: u8) -> Report<E> {
+        self.attach(ExitCode::from(code))
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{AnyError, Report};
+use std::error::Error;
+
+impl From<eyre::Report> for Report<AnyError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from(report: eyre::Report) -> Self {
+        Report::from_boxed(report.into())
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Error + Send + Sync + 'static + ?Sized,
+{
+    pub fn into_eyre(self) -> eyre::Report {
+        eyre::Report::new(self)
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Report;
+use std::ffi::{CString, c_char};
+use std::fmt::Display;
+use std::ptr;
+
+struct FfiLayer {
+    context: Option<CString>,
+    file: CString,
+    line: u32,
+}
+
+/// An owned snapshot of a report for C callers. Rust code hands it over with
+/// [`ErrReport::into_raw`]; C code reads it with the `err_report_*`
+/// functions and releases it with [`err_report_free`]. Strings returned by
+/// the accessors stay valid until then.
+pub struct ErrReport {
+    message: CString,
+    layers: Vec<FfiLayer>,
+}
+
+fn c_string(value: impl Display) -> CString {
+    let value = value.to_string().replace('\0', "\u{FFFD}");
+    CString::new(value).expect("interior NUL bytes were replaced")
+}
+
+impl ErrReport {
+    pub fn new<E>(report: &Report<E>) -> Self
+    where
+        E: Display + ?Sized,
+    {
+        let layers = report
+            .frames()
+            .map(|layer| FfiLayer {
+                context: layer.context.as_ref().map(c_string),
+                file: c_string(
+                    layer
+                        .captured_location()
+                        .map_or("", |location| location.file()),
+                ),
+                line: layer
+                    .captured_location()
+                    .map_or(0, |location| location.line()),
+            })
+            .collect();
+        Self {
+            message: c_string(report.inner()),
+            layers,
+        }
+    }
+
+    pub fn into_raw(self) -> *mut ErrReport {
+        Box::into_raw(Box::new(self))
+    }
+
+    fn layer(&self, index: usize) -> Option<&FfiLayer> {
+        self.layers.get(index)
+    }
+}
+
+impl<E> From<Report<E>> for ErrReport
+where
+    E: Display + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        Self::new(&report)
+    }
+}
+
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+unsafe fn get<'a>(report: *const ErrReport) -> Option<&'a ErrReport> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { report.as_ref() }
+}
+
+/// The inner error's message, or null if `report` is null.
+///
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_message(report: *const ErrReport) -> *const c_char {
+    // SAFETY: forwarded from the caller.
+    unsafe { get(report) }.map_or(ptr::null(), |report| report.message.as_ptr())
+}
+
+/// The number of layers, newest first, or 0 if `report` is null.
+///
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_layer_count(report: *const ErrReport) -> usize {
+    // SAFETY: forwarded from the caller.
+    unsafe { get(report) }.map_or(0, |report| report.layers.len())
+}
+
+/// The context of a layer, or null if it has none or `index` is out of range.
+///
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_layer_context(
+    report: *const ErrReport,
+    index: usize,
+) -> *const c_char {
+    // SAFETY: forwarded from the caller.
+    unsafe { get(report) }
+        .and_then(|report| report.layer(index)?.context.as_ref())
+        .map_or(ptr::null(), |context| context.as_ptr())
+}
+
+/// The file of a layer, or null if `index` is out of range. Empty when
+/// locations are not captured.
+///
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_layer_file(
+    report: *const ErrReport,
+    index: usize,
+) -> *const c_char {
+    // SAFETY: forwarded from the caller.
+    unsafe { get(report) }
+        .and_then(|report| report.layer(index))
+        .map_or(ptr::null(), |layer| layer.file.as_ptr())
+}
+
+/// The line of a layer, or 0 if `index` is out of range or locations are not
+/// captured.
+///
+/// # Safety
+///
+/// `report` must be null or a live pointer from [`ErrReport::into_raw`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_layer_line(report: *const ErrReport, index: usize) -> u32 {
+    // SAFETY: forwarded from the caller.
+    unsafe { get(report) }
+        .and_then(|report| report.layer(index))
+        .map_or(0, |layer| layer.line)
+}
+
+/// Releases a report. Null is ignored.
+///
+/// # Safety
+///
+/// `report` must be null or a pointer from [`ErrReport::into_raw`] that has
+/// not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn err_report_free(report: *mut ErrReport) {
+    if !report.is_null() {
+        // SAFETY: the pointer came from `Box::into_raw` and is freed once.
+        drop(unsafe { Box::from_raw(report) });
+    }
+}
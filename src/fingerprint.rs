@@ -0,0 +1,140 @@
+use crate::Report;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::Display;
+
+// FNV-1a keeps fingerprints stable across builds and Rust versions, unlike
+// `DefaultHasher`.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        // Separates fields so ("ab", "c") and ("a", "bc") differ.
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// Groups identical failures: hashes the original error type, the error
+    /// code and the origin location, but never the message.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv::new();
+        hasher.write((self.error_type)().as_bytes());
+        hasher.write(self.code().unwrap_or_default().as_bytes());
+        let origin = self.origin();
+        hasher.write(origin.file().as_bytes());
+        hasher.write(&origin.line().to_le_bytes());
+        hasher.write(&origin.column().to_le_bytes());
+        hasher.0
+    }
+
+    /// [`Report::fingerprint`] as sixteen hex digits.
+    pub fn fingerprint_hex(&self) -> String {
+        format!("{:016x}", self.fingerprint())
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Display + ?Sized,
+{
+    /// Compares the original error type, the inner error's message, the
+    /// error code and the context strings, ignoring locations, timestamps
+    /// and attachments.
+    pub fn semantic_eq<F>(&self, other: &Report<F>) -> bool
+    where
+        F: Display + ?Sized,
+    {
+        (self.error_type)() == (other.error_type)()
+            && self.code() == other.code()
+            && self.inner.to_string() == other.inner.to_string()
+            && self.layers.len() == other.layers.len()
+            && self
+                .layers
+                .iter()
+                .zip(other.layers.iter())
+                .all(|(a, b)| a.context_str() == b.context_str())
+    }
+
+    /// A hash that agrees with [`Report::semantic_eq`]: semantically equal
+    /// reports hash the same, across builds too.
+    pub fn semantic_hash(&self) -> u64 {
+        let mut hasher = Fnv::new();
+        hasher.write((self.error_type)().as_bytes());
+        hasher.write(self.code().unwrap_or_default().as_bytes());
+        hasher.write(self.inner.to_string().as_bytes());
+        for layer in &self.layers {
+            match layer.context_str() {
+                Some(context) => hasher.write(context.as_bytes()),
+                None => hasher.write(&[]),
+            }
+        }
+        hasher.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Report, StringError};
+
+    fn boom() -> Report<StringError> {
+        Report::msg("boom")
+    }
+
+    #[test]
+    fn fingerprint_follows_the_origin() {
+        assert_eq!(
+            boom().fingerprint(),
+            boom().add_context("retry").fingerprint()
+        );
+        if !cfg!(feature = "no-location") {
+            assert_ne!(boom().fingerprint(), Report::msg("boom").fingerprint());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn fingerprint_ignores_scopes() {
+        let scoped = crate::scope::in_scope("loading", boom);
+        assert_eq!(scoped.fingerprint(), boom().fingerprint());
+    }
+
+    #[test]
+    fn semantic_eq_ignores_locations_and_attachments() {
+        let a = boom().add_context("loading");
+        let b = Report::msg("boom").add_context("loading").attach(7_u32);
+        if !cfg!(feature = "no-location") {
+            assert_ne!(a.origin(), b.origin());
+        }
+        assert!(a.semantic_eq(&b));
+        assert_eq!(a.semantic_hash(), b.semantic_hash());
+        let c = boom().add_context("saving");
+        assert!(!a.semantic_eq(&c));
+        assert_ne!(a.semantic_hash(), c.semantic_hash());
+    }
+
+    #[cfg(feature = "timestamps")]
+    #[test]
+    fn semantic_eq_ignores_timestamps() {
+        let a = boom();
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        let b = boom();
+        let instant =
+            |report: &Report<StringError>| report.origin_layer().timestamp().map(|at| at.instant());
+        assert_ne!(instant(&a), instant(&b));
+        assert!(a.semantic_eq(&b));
+        assert_eq!(a.semantic_hash(), b.semantic_hash());
+    }
+}
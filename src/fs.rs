@@ -0,0 +1,88 @@
+use crate::{Report, ResultIoExt};
+use std::io;
+use std::path::{Path, PathBuf};
+
+type Result<T> = std::result::Result<T, Report<io::Error>>;
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    std::fs::read(path).with_operation("read", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    std::fs::read_to_string(path).with_operation("read", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::write(path, contents).with_operation("write", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn create_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::create_dir(path).with_operation("create directory", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::create_dir_all(path).with_operation("create directory", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn remove_file<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::remove_file(path).with_operation("remove", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn remove_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::remove_dir(path).with_operation("remove directory", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn remove_dir_all<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::remove_dir_all(path).with_operation("remove directory", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn metadata<P: AsRef<Path>>(path: P) -> Result<std::fs::Metadata> {
+    let path = path.as_ref();
+    std::fs::metadata(path).with_operation("read metadata of", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn read_dir<P: AsRef<Path>>(path: P) -> Result<std::fs::ReadDir> {
+    let path = path.as_ref();
+    std::fs::read_dir(path).with_operation("read directory", path)
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+    let path = path.as_ref();
+    std::fs::canonicalize(path).with_operation("canonicalize", path)
+}
+
+/// Namespace for opening [`std::fs::File`]s.
+pub enum File {}
+
+impl File {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<std::fs::File> {
+        let path = path.as_ref();
+        std::fs::File::open(path).with_operation("open", path)
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<std::fs::File> {
+        let path = path.as_ref();
+        std::fs::File::create(path).with_operation("create", path)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{ContextValue, Layer, Report, caller};
+use alloc::boxed::Box;
+use core::fmt::Display;
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+pub struct ReportFuture<F, M> {
+    future: F,
+    map: Option<M>,
+}
+
+impl<F, M, T, E, E2> Future for ReportFuture<F, M>
+where
+    F: Future<Output = Result<T, E>>,
+    M: FnOnce(E) -> E2,
+{
+    type Output = Result<T, E2>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out of `self`; `map` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(Ok(value)),
+            Poll::Ready(Err(e)) => {
+                let map = this
+                    .map
+                    .take()
+                    .expect("ReportFuture polled after completion");
+                Poll::Ready(Err(map(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn context_layer<E, Ctx>(
+    mut report: Report<E>,
+    context: Ctx,
+    location: &'static Location<'static>,
+) -> Report<E>
+where
+    E: ?Sized,
+    Ctx: Display + Send + Sync + 'static,
+{
+    let mut layer = Layer::new(location);
+    layer.context = Some(ContextValue::new(context));
+    report.add_layer(layer);
+    report
+}
+
+pub trait FutureReportExt<T, E>: Future<Output = Result<T, Report<E>>> + Sized
+where
+    E: ?Sized,
+{
+    fn context<Ctx>(self, context: Ctx) -> ReportFuture<Self, impl FnOnce(Report<E>) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static;
+
+    fn with_context<Ctx, F>(self, f: F) -> ReportFuture<Self, impl FnOnce(Report<E>) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static,
+        F: FnOnce() -> Ctx;
+}
+
+impl<Fut, T, E> FutureReportExt<T, E> for Fut
+where
+    Fut: Future<Output = Result<T, Report<E>>>,
+    E: ?Sized,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn context<Ctx>(self, context: Ctx) -> ReportFuture<Self, impl FnOnce(Report<E>) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static,
+    {
+        let location = caller();
+        ReportFuture {
+            future: self,
+            map: Some(move |report| context_layer(report, context, location)),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn with_context<Ctx, F>(self, f: F) -> ReportFuture<Self, impl FnOnce(Report<E>) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static,
+        F: FnOnce() -> Ctx,
+    {
+        let location = caller();
+        ReportFuture {
+            future: self,
+            map: Some(move |report| context_layer(report, f(), location)),
+        }
+    }
+}
+
+pub trait FutureIntoReportExt<T, E>: Future<Output = Result<T, E>> + Sized {
+    fn report(self) -> ReportFuture<Self, impl FnOnce(E) -> Report<E>>;
+
+    fn report_with_context<Ctx>(
+        self,
+        context: Ctx,
+    ) -> ReportFuture<Self, impl FnOnce(E) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static;
+}
+
+impl<Fut, T, E> FutureIntoReportExt<T, E> for Fut
+where
+    Fut: Future<Output = Result<T, E>>,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report(self) -> ReportFuture<Self, impl FnOnce(E) -> Report<E>> {
+        let location = caller();
+        ReportFuture {
+            future: self,
+            map: Some(move |e: E| Report::from_boxed_at(Box::new(e), location)),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_with_context<Ctx>(
+        self,
+        context: Ctx,
+    ) -> ReportFuture<Self, impl FnOnce(E) -> Report<E>>
+    where
+        Ctx: Display + Send + Sync + 'static,
+    {
+        let location = caller();
+        ReportFuture {
+            future: self,
+            map: Some(move |e: E| Report::from_boxed_at(Box::new(e), location).context(context)),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::Report;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+
+pub struct GithubAnnotation<'a, E>
+where
+    E: ?Sized,
+{
+    report: &'a Report<E>,
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+impl<E> Display for GithubAnnotation<'_, E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("::error")?;
+        let mut separator = " ";
+        if let Some(location) = self.report.origin_layer().captured_location() {
+            write!(
+                f,
+                " file={},line={},col={}",
+                escape_property(location.file()),
+                location.line(),
+                location.column()
+            )?;
+            separator = ",";
+        }
+        if let Some(code) = self.report.code() {
+            write!(f, "{}title={}", separator, escape_property(code))?;
+        }
+        write!(f, "::{}", escape_data(&self.report.to_string()))
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn github_annotation(&self) -> GithubAnnotation<'_, E> {
+        GithubAnnotation { report: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn annotation_escapes_the_message_and_points_at_the_origin() {
+        let report = Report::msg("a\nb").add_context("c");
+        let message = escape_data(&report.to_string());
+        assert!(message.starts_with("a%0Ab"));
+        let expected = match report.origin_layer().captured_location() {
+            Some(location) => format!(
+                "::error file={},line={},col={}::{}",
+                escape_property(location.file()),
+                location.line(),
+                location.column(),
+                message
+            ),
+            None => format!("::error::{}", message),
+        };
+        assert_eq!(report.github_annotation().to_string(), expected);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::sync::RwLock;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::fmt::{Display, Formatter};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+type Provider = Arc<dyn Fn(&mut Metadata) + Send + Sync>;
+
+static PROVIDER: RwLock<Option<Provider>> = RwLock::new(None);
+static HAS_PROVIDER: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Metadata(BTreeMap<String, String>);
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Display) {
+        self.0.insert(key.into(), value.to_string());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl Display for Metadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (index, (key, value)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn set_provider<F>(provider: F)
+where
+    F: Fn(&mut Metadata) + Send + Sync + 'static,
+{
+    *PROVIDER.write() = Some(Arc::new(provider));
+    HAS_PROVIDER.store(true, Ordering::Release);
+}
+
+pub fn clear_provider() {
+    HAS_PROVIDER.store(false, Ordering::Release);
+    *PROVIDER.write() = None;
+}
+
+pub(crate) fn collect() -> Option<Metadata> {
+    if !HAS_PROVIDER.load(Ordering::Acquire) {
+        return None;
+    }
+    // Clone the provider out so it can itself create reports without deadlocking.
+    let provider = PROVIDER.read().clone()?;
+    let mut metadata = Metadata::new();
+    provider(&mut metadata);
+    (!metadata.is_empty()).then_some(metadata)
+}
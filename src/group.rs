@@ -0,0 +1,200 @@
+use crate::render::Indented;
+use crate::{AnyError, IntoAnyError, Report};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+#[derive(Default)]
+pub struct ReportGroup {
+    reports: Vec<Report<AnyError>>,
+}
+
+impl ReportGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<E>(&mut self, report: Report<E>)
+    where
+        E: IntoAnyError + ?Sized,
+    {
+        self.reports.push(report.into_any());
+    }
+
+    pub fn len(&self) -> usize {
+        self.reports.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Report<AnyError>> {
+        self.reports.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<Report<AnyError>> {
+        self.reports
+    }
+}
+
+impl<E> Extend<Report<E>> for ReportGroup
+where
+    E: IntoAnyError + ?Sized,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Report<E>>,
+    {
+        self.reports.extend(iter.into_iter().map(Report::into_any));
+    }
+}
+
+impl<E> FromIterator<Report<E>> for ReportGroup
+where
+    E: IntoAnyError + ?Sized,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Report<E>>,
+    {
+        let mut group = Self::new();
+        group.extend(iter);
+        group
+    }
+}
+
+impl IntoIterator for ReportGroup {
+    type Item = Report<AnyError>;
+    type IntoIter = alloc::vec::IntoIter<Report<AnyError>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reports.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ReportGroup {
+    type Item = &'a Report<AnyError>;
+    type IntoIter = core::slice::Iter<'a, Report<AnyError>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.reports.iter()
+    }
+}
+
+impl Debug for ReportGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(&self.reports).finish()
+    }
+}
+
+impl Display for ReportGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.reports.len() {
+            1 => write!(f, "1 error occurred:")?,
+            count => write!(f, "{} errors occurred:", count)?,
+        }
+        for (index, report) in self.reports.iter().enumerate() {
+            write!(f, "\n  {}. ", index + 1)?;
+            let alternate = f.alternate();
+            let mut indented = Indented { f, indent: "     " };
+            if alternate {
+                write!(indented, "{:#}", report)?;
+            } else {
+                write!(indented, "{}", report)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for ReportGroup {}
+
+pub trait CollectReportsExt<T> {
+    fn collect_reports(self) -> Result<Vec<T>, ReportGroup>
+    where
+        Self: Sized;
+}
+
+impl<I, T, E> CollectReportsExt<T> for I
+where
+    I: Iterator<Item = Result<T, Report<E>>>,
+    E: IntoAnyError + ?Sized,
+{
+    fn collect_reports(self) -> Result<Vec<T>, ReportGroup> {
+        let mut values = Vec::new();
+        let mut group = ReportGroup::new();
+        for item in self {
+            match item {
+                Ok(value) => values.push(value),
+                Err(report) => group.push(report),
+            }
+        }
+        if group.is_empty() {
+            Ok(values)
+        } else {
+            Err(group)
+        }
+    }
+}
+
+/// Accumulates failures across a validation pass instead of stopping at the
+/// first one.
+#[derive(Debug, Default)]
+pub struct ReportCollector {
+    group: ReportGroup,
+}
+
+impl ReportCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push<E>(&mut self, report: Report<E>)
+    where
+        E: IntoAnyError + ?Sized,
+    {
+        self.group.push(report);
+    }
+
+    /// The value on success; on failure, keeps the report and returns `None`.
+    pub fn check<T, E>(&mut self, result: Result<T, Report<E>>) -> Option<T>
+    where
+        E: IntoAnyError + ?Sized,
+    {
+        result.map_err(|report| self.push(report)).ok()
+    }
+
+    /// Like [`ReportCollector::check`], adding `label` as a layer on
+    /// failure, e.g. the name of the field being validated.
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn check_labeled<T, E, L>(&mut self, label: L, result: Result<T, Report<E>>) -> Option<T>
+    where
+        E: IntoAnyError + ?Sized,
+        L: Display + Send + Sync + 'static,
+    {
+        match result {
+            Ok(value) => Some(value),
+            Err(report) => {
+                self.push(report.add_context(label));
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.group.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.group.is_empty()
+    }
+
+    pub fn finish(self) -> Result<(), ReportGroup> {
+        if self.group.is_empty() {
+            Ok(())
+        } else {
+            Err(self.group)
+        }
+    }
+}
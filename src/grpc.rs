@@ -0,0 +1,59 @@
+use crate::{AsDynError, Report, config};
+use std::fmt::Display;
+use tonic::metadata::{BinaryMetadataValue, MetadataMap};
+use tonic::{Code, Status};
+
+fn code_for_status(status: u16) -> Code {
+    match status {
+        400 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 => Code::NotFound,
+        409 => Code::AlreadyExists,
+        412 => Code::FailedPrecondition,
+        429 => Code::ResourceExhausted,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        502 | 503 => Code::Unavailable,
+        504 => Code::DeadlineExceeded,
+        _ => Code::Internal,
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_grpc_code(self, code: Code) -> Report<E> {
+        self.attach(code)
+    }
+
+    pub fn grpc_code(&self) -> Code
+    where
+        E: AsDynError,
+    {
+        if let Some(code) = self.request_ref::<Code>() {
+            return *code;
+        }
+        self.status().map(code_for_status).unwrap_or(Code::Internal)
+    }
+}
+
+impl<E> From<Report<E>> for Status
+where
+    E: AsDynError + Display + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        report.finalize();
+        let code = report.grpc_code();
+        if !config::expose_details() {
+            return Status::new(code, report.inner.to_string());
+        }
+        let mut metadata = MetadataMap::new();
+        metadata.insert_bin(
+            "err-report-bin",
+            BinaryMetadataValue::from_bytes(format!("{:#}", report).as_bytes()),
+        );
+        Status::with_metadata(code, report.to_string(), metadata)
+    }
+}
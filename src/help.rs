@@ -0,0 +1,30 @@
+use crate::Report;
+use alloc::string::String;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HelpUrl(pub String);
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn suggestion(self, suggestion: impl Into<String>) -> Report<E> {
+        self.attach(Suggestion(suggestion.into()))
+    }
+
+    pub fn help_url(self, url: impl Into<String>) -> Report<E> {
+        self.attach(HelpUrl(url.into()))
+    }
+
+    pub fn suggestions(&self) -> impl Iterator<Item = &str> {
+        self.attachments::<Suggestion>()
+            .map(|suggestion| suggestion.0.as_str())
+    }
+
+    pub fn help_urls(&self) -> impl Iterator<Item = &str> {
+        self.attachments::<HelpUrl>().map(|url| url.0.as_str())
+    }
+}
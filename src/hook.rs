@@ -0,0 +1,317 @@
+use crate::config::{Config, Order, Verbosity};
+use crate::sync::RwLock;
+use crate::{Chain, Layer, Report, Trace, render};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+use core::fmt::{Debug, Display, Formatter};
+
+type AttachmentHook = Arc<dyn Fn(&dyn Any, &mut Formatter<'_>) -> core::fmt::Result + Send + Sync>;
+
+static HANDLER: RwLock<Option<Arc<dyn ReportHandler>>> = RwLock::new(None);
+static ATTACHMENT_HOOKS: RwLock<Vec<(TypeId, AttachmentHook)>> = RwLock::new(Vec::new());
+
+pub trait ReportHandler: Send + Sync + 'static {
+    fn display(&self, report: &ReportView<'_>, f: &mut Formatter<'_>) -> core::fmt::Result {
+        report.write_default(f)
+    }
+
+    fn debug(&self, report: &ReportView<'_>, f: &mut Formatter<'_>) -> core::fmt::Result {
+        report.write_default_debug(f)
+    }
+}
+
+pub fn set_handler<H>(handler: H)
+where
+    H: ReportHandler,
+{
+    *HANDLER.write() = Some(Arc::new(handler));
+}
+
+pub fn clear_handler() {
+    *HANDLER.write() = None;
+}
+
+pub(crate) fn handler() -> Option<Arc<dyn ReportHandler>> {
+    HANDLER.read().clone()
+}
+
+pub fn register<T>(
+    hook: impl Fn(&T, &mut Formatter<'_>) -> core::fmt::Result + Send + Sync + 'static,
+) where
+    T: Send + Sync + 'static,
+{
+    let hook: AttachmentHook =
+        Arc::new(move |attachment, f| match attachment.downcast_ref::<T>() {
+            Some(attachment) => hook(attachment, f),
+            None => Ok(()),
+        });
+    let mut hooks = ATTACHMENT_HOOKS.write();
+    hooks.retain(|(type_id, _)| *type_id != TypeId::of::<T>());
+    hooks.push((TypeId::of::<T>(), hook));
+}
+
+pub fn unregister<T>()
+where
+    T: Send + Sync + 'static,
+{
+    ATTACHMENT_HOOKS
+        .write()
+        .retain(|(type_id, _)| *type_id != TypeId::of::<T>());
+}
+
+fn attachment_hook(attachment: &dyn Any) -> Option<AttachmentHook> {
+    let type_id = attachment.type_id();
+    ATTACHMENT_HOOKS
+        .read()
+        .iter()
+        .find(|(id, _)| *id == type_id)
+        .map(|(_, hook)| hook.clone())
+}
+
+/// Displays an attachment through its registered hook, if there is one.
+pub struct HookedAttachment<'a> {
+    attachment: &'a dyn Any,
+    hook: AttachmentHook,
+}
+
+impl Display for HookedAttachment<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        (self.hook)(self.attachment, f)
+    }
+}
+
+pub fn hooked(layer: &Layer) -> impl Iterator<Item = HookedAttachment<'_>> {
+    layer.attachments.iter().filter_map(|attachment| {
+        let attachment: &dyn Any = &**attachment;
+        attachment_hook(attachment).map(|hook| HookedAttachment { attachment, hook })
+    })
+}
+
+pub(crate) struct DebugAsDisplay<'a, E>(pub(crate) &'a E)
+where
+    E: ?Sized;
+
+impl<E> Display for DebugAsDisplay<'_, E>
+where
+    E: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.0, f)
+    }
+}
+
+pub(crate) struct Message<'a, E>(pub(crate) &'a E)
+where
+    E: ?Sized;
+
+impl<E> Display for Message<'_, E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+struct DisplayAsDebug<'a>(&'a dyn Display);
+
+impl Debug for DisplayAsDebug<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+/// A type-erased view of a report handed to [`ReportHandler`]s. Inside
+/// `debug`, the message renders the inner error's `Debug` output.
+pub struct ReportView<'a> {
+    message: &'a dyn Display,
+    layers: &'a [Layer],
+    backtrace: Option<&'a Trace>,
+    #[cfg(feature = "spantrace")]
+    span_trace: Option<&'a tracing_error::SpanTrace>,
+    sources: Chain<'a>,
+    #[cfg(feature = "color")]
+    to_stderr: bool,
+}
+
+/// Renders with the installed [`Config`] adjusted for one call, as returned
+/// by [`Report::display_root_first`], [`Report::display_stable`],
+/// [`Report::display_with`] and [`Report::compact`].
+pub struct DisplayWithConfig<'a, E>
+where
+    E: ?Sized,
+{
+    report: &'a Report<E>,
+    config: Config,
+}
+
+impl<E> Display for DisplayWithConfig<'_, E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let inner = Message(&*self.report.inner);
+        let view = ReportView::new(self.report, &inner);
+        render::write(&view, &self.config, f)
+    }
+}
+
+/// Renders through the handler like `Display`, for stderr.
+#[cfg(feature = "std")]
+pub(crate) struct DisplayStderr<'a, E>(pub(crate) &'a Report<E>)
+where
+    E: ?Sized;
+
+#[cfg(feature = "std")]
+impl<E> Display for DisplayStderr<'_, E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let inner = Message(&*self.0.inner);
+        let view = ReportView::new(self.0, &inner);
+        #[cfg(feature = "color")]
+        let view = view.for_stderr();
+        match handler() {
+            Some(handler) => handler.display(&view, f),
+            None => view.write_default(f),
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// Renders the single-line format with a custom layer separator.
+    pub fn display_with(&self, separator: &str) -> DisplayWithConfig<'_, E> {
+        self.display_with_config(Config::current().with_separator(separator))
+    }
+
+    /// Renders only the message and the origin location.
+    pub fn compact(&self) -> DisplayWithConfig<'_, E> {
+        self.display_with_config(Config::current().with_verbosity(Verbosity::Compact))
+    }
+
+    pub fn display_root_first(&self) -> DisplayWithConfig<'_, E> {
+        self.display_with_config(Config::current().with_order(Order::RootFirst))
+    }
+
+    /// Renders without line numbers, absolute paths or other details that
+    /// change between runs, for snapshot tests.
+    pub fn display_stable(&self) -> DisplayWithConfig<'_, E> {
+        self.display_with_config(Config::current().with_stable_output())
+    }
+
+    fn display_with_config(&self, config: Config) -> DisplayWithConfig<'_, E> {
+        DisplayWithConfig {
+            report: self,
+            config,
+        }
+    }
+}
+
+impl<'a> ReportView<'a> {
+    pub(crate) fn new<E>(report: &'a Report<E>, message: &'a dyn Display) -> Self
+    where
+        E: ?Sized,
+    {
+        Self {
+            message,
+            layers: &report.layers,
+            backtrace: report.backtrace.as_deref(),
+            #[cfg(feature = "spantrace")]
+            span_trace: report.span_trace.as_deref(),
+            sources: report.added_causes(),
+            #[cfg(feature = "color")]
+            to_stderr: false,
+        }
+    }
+
+    /// Marks the view as rendered straight to stderr, so
+    /// [`ColorChoice::Auto`](crate::config::ColorChoice::Auto) may color it.
+    #[cfg(feature = "color")]
+    pub(crate) fn for_stderr(mut self) -> Self {
+        self.to_stderr = true;
+        self
+    }
+
+    /// The installed config, as it applies to this view's writer.
+    fn config(&self) -> Config {
+        let config = Config::current();
+        #[cfg(feature = "color")]
+        if self.to_stderr {
+            return config.with_stderr();
+        }
+        config
+    }
+
+    pub fn message(&self) -> &dyn Display {
+        self.message
+    }
+
+    /// The layers in the order they were added, origin first.
+    pub fn layers(&self) -> &'a [Layer] {
+        self.layers
+    }
+
+    /// The layer the report was created at, past any scope layers.
+    pub fn origin_layer(&self) -> &'a Layer {
+        &self.layers[crate::origin_index(self.layers)]
+    }
+
+    pub fn request_ref<T>(&self) -> Option<&'a T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attachments::<T>().next()
+    }
+
+    pub fn attachments<T>(&self) -> impl Iterator<Item = &'a T> + use<'a, T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.layers
+            .iter()
+            .rev()
+            .flat_map(|layer| layer.attachments.iter())
+            .filter_map(|attachment| attachment.downcast_ref::<T>())
+    }
+
+    pub fn backtrace(&self) -> Option<&dyn Display> {
+        self.backtrace.map(|backtrace| backtrace as &dyn Display)
+    }
+
+    #[cfg(feature = "spantrace")]
+    pub fn span_trace(&self) -> Option<&'a tracing_error::SpanTrace> {
+        self.span_trace
+    }
+
+    /// The errors after the inner one: a cause added with
+    /// [`Report::with_cause`] and its sources. The view does not know the
+    /// inner error's type, so its own source is not included.
+    pub fn sources(&self) -> Chain<'a> {
+        self.sources.clone()
+    }
+
+    pub fn write_default(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        render::write(self, &self.config(), f)
+    }
+
+    /// `{:?}` lists the layers alongside the inner error; `{:#?}` renders
+    /// the full multi-line report.
+    pub fn write_default_debug(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let config = self.config();
+        if f.alternate() {
+            return render::pretty(self, &config, f);
+        }
+        let mut debug = f.debug_struct("Report");
+        debug.field("inner", &DisplayAsDebug(self.message));
+        debug.field("layers", &render::DebugLayers(self, &config));
+        if let Some(backtrace) = self.backtrace {
+            debug.field("backtrace", backtrace);
+        }
+        debug.finish()
+    }
+}
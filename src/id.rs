@@ -0,0 +1,28 @@
+use crate::Report;
+use std::fmt::{Display, Formatter};
+use uuid::Uuid;
+
+pub(crate) struct ReportId(pub(crate) Uuid);
+
+impl ReportId {
+    pub(crate) fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Display for ReportId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// The random ID assigned when the report was created, for matching a
+    /// user-facing error ID with server logs.
+    pub fn id(&self) -> Option<Uuid> {
+        self.request_ref::<ReportId>().map(|id| id.0)
+    }
+}
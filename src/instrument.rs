@@ -0,0 +1,57 @@
+use std::panic::Location;
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::Level;
+
+static LEVEL: AtomicU8 = AtomicU8::new(1);
+
+pub(crate) fn set_level(level: Level) {
+    let value = match level {
+        Level::TRACE => 0,
+        Level::DEBUG => 1,
+        Level::INFO => 2,
+        Level::WARN => 3,
+        Level::ERROR => 4,
+    };
+    LEVEL.store(value, Ordering::Relaxed);
+}
+
+pub(crate) fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::TRACE,
+        2 => Level::INFO,
+        3 => Level::WARN,
+        4 => Level::ERROR,
+        _ => Level::DEBUG,
+    }
+}
+
+macro_rules! emit {
+    ($($fields:tt)*) => {
+        match level() {
+            Level::TRACE => tracing::event!(Level::TRACE, $($fields)*),
+            Level::DEBUG => tracing::event!(Level::DEBUG, $($fields)*),
+            Level::INFO => tracing::event!(Level::INFO, $($fields)*),
+            Level::WARN => tracing::event!(Level::WARN, $($fields)*),
+            Level::ERROR => tracing::event!(Level::ERROR, $($fields)*),
+        }
+    };
+}
+
+pub(crate) fn report_created(error_type: &'static str, location: &'static Location<'static>) {
+    emit!(
+        error.r#type = error_type,
+        file = location.file(),
+        line = location.line(),
+        column = location.column(),
+        "report created"
+    );
+}
+
+pub(crate) fn layer_added(location: &'static Location<'static>) {
+    emit!(
+        file = location.file(),
+        line = location.line(),
+        column = location.column(),
+        "report layer added"
+    );
+}
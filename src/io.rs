@@ -0,0 +1,135 @@
+use crate::{Layer, Report, caller};
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The path, and optionally the operation, an I/O error happened on. Added
+/// as both the layer context and an attachment by [`ResultIoExt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathContext {
+    operation: Option<&'static str>,
+    path: PathBuf,
+}
+
+impl PathContext {
+    pub fn new(operation: Option<&'static str>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            operation,
+            path: path.into(),
+        }
+    }
+
+    pub fn operation(&self) -> Option<&'static str> {
+        self.operation
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Display for PathContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.operation {
+            Some(operation) => write!(f, "failed to {} `{}`", operation, self.path.display()),
+            None => write!(f, "path `{}`", self.path.display()),
+        }
+    }
+}
+
+impl Report<io::Error> {
+    pub fn kind(&self) -> io::ErrorKind {
+        self.inner.kind()
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == io::ErrorKind::NotFound
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// The most recent path attached through [`ResultIoExt`].
+    pub fn path(&self) -> Option<&Path> {
+        self.request_ref::<PathContext>().map(PathContext::path)
+    }
+}
+
+pub trait ResultIoExt<T> {
+    fn with_path<P>(self, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>;
+
+    fn with_operation<P>(self, operation: &'static str, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>;
+}
+
+impl<T> ResultIoExt<T> for Result<T, io::Error> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn with_path<P>(self, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(e) => Err(attach_path(
+                Report::new(e),
+                PathContext::new(None, path.as_ref()),
+            )),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn with_operation<P>(self, operation: &'static str, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(e) => Err(attach_path(
+                Report::new(e),
+                PathContext::new(Some(operation), path.as_ref()),
+            )),
+        }
+    }
+}
+
+impl<T> ResultIoExt<T> for Result<T, Report<io::Error>> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn with_path<P>(self, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(mut report) => {
+                report.add_layer(Layer::new(caller()));
+                Err(attach_path(report, PathContext::new(None, path.as_ref())))
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn with_operation<P>(self, operation: &'static str, path: P) -> Result<T, Report<io::Error>>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            Ok(value) => Ok(value),
+            Err(mut report) => {
+                report.add_layer(Layer::new(caller()));
+                Err(attach_path(
+                    report,
+                    PathContext::new(Some(operation), path.as_ref()),
+                ))
+            }
+        }
+    }
+}
+
+fn attach_path(report: Report<io::Error>, context: PathContext) -> Report<io::Error> {
+    report.context(context.clone()).attach(context)
+}
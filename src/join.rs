@@ -0,0 +1,137 @@
+use crate::{IntoAnyError, Report, ReportGroup};
+use core::future::Future;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Tuples of results that succeed together or fail with every report, as
+/// used by [`try_join!`](crate::try_join) and [`zip_report`].
+pub trait TryJoin {
+    type Output;
+
+    fn try_join(self) -> Result<Self::Output, ReportGroup>;
+}
+
+/// Both values, or the reports of whichever failed.
+pub fn zip_report<A, EA, B, EB>(
+    a: Result<A, Report<EA>>,
+    b: Result<B, Report<EB>>,
+) -> Result<(A, B), ReportGroup>
+where
+    EA: IntoAnyError + ?Sized,
+    EB: IntoAnyError + ?Sized,
+{
+    (a, b).try_join()
+}
+
+#[doc(hidden)]
+pub enum MaybeDone<F>
+where
+    F: Future,
+{
+    Future(F),
+    Done(F::Output),
+    Taken,
+}
+
+impl<F> MaybeDone<F>
+where
+    F: Future,
+{
+    fn poll_done(self: Pin<&mut Self>, cx: &mut Context<'_>) -> bool {
+        // SAFETY: the future is only dropped in place, never moved.
+        let this = unsafe { self.get_unchecked_mut() };
+        if let MaybeDone::Future(future) = this {
+            match unsafe { Pin::new_unchecked(future) }.poll(cx) {
+                Poll::Ready(output) => *this = MaybeDone::Done(output),
+                Poll::Pending => return false,
+            }
+        }
+        true
+    }
+
+    fn take(&mut self) -> F::Output {
+        match mem::replace(self, MaybeDone::Taken) {
+            MaybeDone::Done(output) => output,
+            _ => panic!("Join polled after completion"),
+        }
+    }
+}
+
+/// Polls a tuple of futures until all of them are done.
+#[doc(hidden)]
+pub struct Join<T>(pub T);
+
+macro_rules! impl_join {
+    ($($value:ident $error:ident $future:ident $var:ident),+) => {
+        impl<$($value, $error),+> TryJoin for ($(Result<$value, Report<$error>>,)+)
+        where
+            $($error: IntoAnyError + ?Sized,)+
+        {
+            type Output = ($($value,)+);
+
+            fn try_join(self) -> Result<Self::Output, ReportGroup> {
+                let mut group = ReportGroup::new();
+                let ($($var,)+) = self;
+                $(let $var = $var.map_err(|report| group.push(report)).ok();)+
+                match ($($var,)+) {
+                    ($(Some($var),)+) => Ok(($($var,)+)),
+                    _ => Err(group),
+                }
+            }
+        }
+
+        impl<$($future),+> Future for Join<($(MaybeDone<$future>,)+)>
+        where
+            $($future: Future,)+
+        {
+            type Output = ($($future::Output,)+);
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                // SAFETY: the futures are pinned in place inside `self`.
+                let ($($var,)+) = unsafe { &mut self.get_unchecked_mut().0 };
+                let mut done = true;
+                $(done &= unsafe { Pin::new_unchecked(&mut *$var) }.poll_done(cx);)+
+                if !done {
+                    return Poll::Pending;
+                }
+                Poll::Ready(($($var.take(),)+))
+            }
+        }
+    };
+}
+
+impl_join!(A EA FA a);
+impl_join!(A EA FA a, B EB FB b);
+impl_join!(A EA FA a, B EB FB b, C EC FC c);
+impl_join!(A EA FA a, B EB FB b, C EC FC c, D ED FD d);
+impl_join!(A EA FA a, B EB FB b, C EC FC c, D ED FD d, E EE FE e);
+impl_join!(A EA FA a, B EB FB b, C EC FC c, D ED FD d, E EE FE e, F EF FF f);
+impl_join!(A EA FA a, B EB FB b, C EC FC c, D ED FD d, E EE FE e, F EF FF f, G EG FG g);
+impl_join!(A EA FA a, B EB FB b, C EC FC c, D ED FD d, E EE FE e, F EF FF f, G EG FG g, H EH FH h);
+
+/// Evaluates every result and returns a tuple of their values, or a
+/// [`ReportGroup`](crate::ReportGroup) with the report of each one that
+/// failed, in argument order. Takes up to eight results.
+#[macro_export]
+macro_rules! try_join {
+    ($($result:expr),+ $(,)?) => {
+        $crate::TryJoin::try_join(($($result,)+))
+    };
+}
+
+/// Like [`try_join!`](crate::try_join) for futures: awaits them all
+/// concurrently, inside an `async` block or function.
+#[macro_export]
+macro_rules! try_join_async {
+    ($($future:expr),+ $(,)?) => {
+        $crate::TryJoin::try_join(
+            $crate::__private::Join(($(
+                $crate::__private::MaybeDone::Future(::core::future::IntoFuture::into_future(
+                    $future,
+                )),
+            )+))
+            .await,
+        )
+    };
+}
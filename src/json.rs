@@ -0,0 +1,109 @@
+use crate::{NamedSource, Report};
+use serde_json::error::Category;
+use std::fmt::{Display, Formatter};
+
+/// Where in the input a `serde_json` error happened, 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonPosition {
+    line: usize,
+    column: usize,
+}
+
+impl JsonPosition {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for JsonPosition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at line {}, column {}", self.line, self.column)
+    }
+}
+
+fn category(e: &serde_json::Error) -> &'static str {
+    match e.classify() {
+        Category::Io => "I/O error",
+        Category::Syntax => "syntax error",
+        Category::Data => "invalid data",
+        Category::Eof => "unexpected end of input",
+    }
+}
+
+// serde_json counts the bytes consumed on the line, so the offending byte
+// is the one before; column 0 points at the previous line break.
+fn offset(source: &NamedSource, position: JsonPosition) -> usize {
+    let text = source.text();
+    let line_start = text
+        .split_inclusive('\n')
+        .take(position.line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    source.floor((line_start + position.column).saturating_sub(1))
+}
+
+pub trait JsonResultExt<T> {
+    /// Reports the error with its line and column as context.
+    fn parse_report(self) -> Result<T, Report<serde_json::Error>>;
+
+    /// Like [`JsonResultExt::parse_report`], and also attaches the input so
+    /// the pretty rendering can underline the offending position.
+    fn parse_report_with_source(
+        self,
+        name: impl Into<String>,
+        input: &str,
+    ) -> Result<T, Report<serde_json::Error>>;
+}
+
+impl<T> JsonResultExt<T> for Result<T, serde_json::Error> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn parse_report(self) -> Result<T, Report<serde_json::Error>> {
+        match self {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                let position = JsonPosition {
+                    line: e.line(),
+                    column: e.column(),
+                };
+                // Line 0 means serde_json had no position, e.g. for I/O errors.
+                if position.line == 0 {
+                    return Err(Report::new(e));
+                }
+                Err(Report::new(e).context(position).attach(position))
+            }
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn parse_report_with_source(
+        self,
+        name: impl Into<String>,
+        input: &str,
+    ) -> Result<T, Report<serde_json::Error>> {
+        let report = match self.parse_report() {
+            Ok(value) => return Ok(value),
+            Err(report) => report,
+        };
+        let Some(&position) = report.json_position() else {
+            return Err(report);
+        };
+        let message = category(report.inner());
+        let source = NamedSource::new(name, input);
+        let start = offset(&source, position);
+        let end = source.floor(start + 1).max(start);
+        Err(report.with_source(source).label(start..end, message))
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn json_position(&self) -> Option<&JsonPosition> {
+        self.request_ref()
+    }
+}
@@ -1,38 +1,431 @@
-use std::error::Error;
-use std::fmt::{Debug, Display, Formatter};
-use std::ops::{Deref, DerefMut};
-use std::panic::Location;
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "compat-fields", allow(deprecated))]
+#![cfg_attr(feature = "unstable-provider", feature(error_generic_member_access))]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::Any;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Deref, DerefMut};
+use core::panic::Location;
+#[cfg(feature = "std")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "anyhow")]
+mod anyhow;
+mod boxed;
+mod chain;
+pub mod codes;
+pub mod config;
+mod context;
+mod data;
+mod dedup;
+mod elapsed;
+#[cfg(feature = "error-stack")]
+mod error_stack;
+#[cfg(feature = "std")]
+mod exit;
+#[cfg(feature = "eyre")]
+mod eyre;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fingerprint;
+#[cfg(feature = "std")]
+pub mod fs;
+mod future;
+mod github;
+pub mod global;
+mod group;
+#[cfg(feature = "tonic")]
+mod grpc;
+pub mod help;
+pub mod hook;
+#[cfg(feature = "uuid")]
+mod id;
+#[cfg(feature = "tracing")]
+mod instrument;
+#[cfg(feature = "std")]
+mod io;
+mod join;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "log")]
+mod logging;
+mod macros;
+#[cfg(feature = "metrics")]
+pub mod metric;
+#[cfg(feature = "miette")]
+mod miette;
+#[cfg(feature = "napi")]
+mod napi;
+#[cfg(feature = "otel")]
+mod otel;
+#[cfg(feature = "serde_json")]
+mod problem;
+#[cfg(feature = "std")]
+mod process;
+#[cfg(feature = "unstable-provider")]
+mod provider;
+#[cfg(feature = "pyo3")]
+mod pyo3;
+#[cfg(feature = "rayon")]
+mod rayon;
+mod redact;
+mod render;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "sarif")]
+mod sarif;
+#[cfg(feature = "std")]
+pub mod scope;
+mod shared;
+pub mod sink;
+mod snippet;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+pub mod status;
+#[cfg(feature = "futures")]
+mod stream;
+mod sync;
+#[cfg(feature = "sysinfo")]
+mod sysinfo;
+mod testing;
+#[cfg(feature = "thread-info")]
+mod thread;
+#[cfg(feature = "timestamps")]
+mod timestamp;
+#[cfg(feature = "tower")]
+mod tower;
+pub mod transient;
+mod truncate;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "serde")]
+mod wire;
+
+pub use boxed::BoxedReport;
+pub use chain::{AsDynError, Chain, IntoAnyError};
+use chain::{Cause, Caused, Wrapped};
+pub use codes::explain;
+pub use config::{Config, LocationStyle, Order, RedactionMode, Verbosity};
+pub use context::ContextValue;
+pub use data::{LayerData, ReportData};
+pub use elapsed::Elapsed;
+#[cfg(feature = "std")]
+pub use elapsed::timed_context;
+#[cfg(feature = "macros")]
+pub use err_report_macros::{ReportError, context};
+#[cfg(feature = "std")]
+pub use exit::MainResult;
+pub use future::{FutureIntoReportExt, FutureReportExt, ReportFuture};
+pub use github::GithubAnnotation;
+pub use group::{CollectReportsExt, ReportCollector, ReportGroup};
+pub use help::{HelpUrl, Suggestion};
+pub use hook::{DisplayWithConfig, ReportHandler, ReportView};
+#[cfg(feature = "std")]
+pub use io::{PathContext, ResultIoExt};
+pub use join::{TryJoin, zip_report};
+#[cfg(feature = "serde_json")]
+pub use json::{JsonPosition, JsonResultExt};
+#[cfg(feature = "log")]
+pub use logging::LogResultExt;
+#[cfg(feature = "serde_json")]
+pub use problem::ProblemInstance;
+#[cfg(feature = "std")]
+pub use process::{CommandContext, CommandError, CommandReportExt};
+#[cfg(feature = "pyo3")]
+pub use pyo3::ReportError;
+#[cfg(feature = "rayon")]
+pub use rayon::ParallelCollectReportsExt;
+pub use redact::Redacted;
+#[cfg(feature = "reqwest")]
+pub use reqwest::{RequestBuilderReportExt, RequestContext, ReqwestResultExt};
+#[cfg(feature = "std")]
+pub use retry::{Attempt, Attempts, RetryPolicy, retry};
+pub use shared::SharedReport;
+pub use snippet::{Label, NamedSource};
+#[cfg(feature = "sqlx")]
+pub use sqlx::{DatabaseContext, SqlxResultExt};
+pub use status::HttpStatus;
+#[cfg(feature = "futures")]
+pub use stream::{ContextItems, StreamReportExt};
+#[cfg(feature = "sysinfo")]
+pub use sysinfo::SystemInfo;
+#[cfg(feature = "thread-info")]
+pub use thread::ThreadInfo;
+#[cfg(feature = "timestamps")]
+pub use timestamp::Timestamp;
+#[cfg(feature = "tower")]
+pub use tower::{ReportLayer, ReportService, RequestInfo, ResponseFuture};
+#[cfg(feature = "wasm")]
+pub use wasm::{JsException, JsResultExt};
+#[cfg(feature = "serde")]
+pub use wire::DeserializedReport;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::join::{Join, MaybeDone};
+    pub use alloc::format;
+}
 
 pub type AnyError = dyn Error + Send + Sync + 'static;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringError(String);
+
+impl StringError {
+    pub fn new<S>(message: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self(message.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for StringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for StringError {}
+
+impl From<String> for StringError {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
+
+impl From<&str> for StringError {
+    fn from(message: &str) -> Self {
+        Self(message.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoneError;
+
+impl Display for NoneError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("value was None")
+    }
+}
+
+impl Error for NoneError {}
+
+struct DisplayError<T>(T);
+
+impl<T> Debug for DisplayError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.0.to_string(), f)
+    }
+}
+
+impl<T> Display for DisplayError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<T> Error for DisplayError<T> where T: Display {}
+
 pub struct Layer {
-    pub context: Option<Box<dyn Display + Send + Sync + 'static>>,
+    pub context: Option<ContextValue>,
     pub location: &'static Location<'static>,
+    pub attachments: Vec<Box<dyn Any + Send + Sync + 'static>>,
+}
+
+impl Layer {
+    pub fn new(location: &'static Location<'static>) -> Self {
+        Self {
+            context: None,
+            location,
+            #[cfg(not(feature = "timestamps"))]
+            attachments: Vec::new(),
+            #[cfg(feature = "timestamps")]
+            attachments: vec![Box::new(timestamp::Timestamp::now())],
+        }
+    }
+
+    pub fn context_str(&self) -> Option<String> {
+        self.context.as_ref().map(|context| context.to_string())
+    }
+
+    pub fn context(&self) -> Option<&ContextValue> {
+        self.context.as_ref()
+    }
+
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The location, or `None` when built with the `no-location` feature, in
+    /// which case [`Layer::location`] is a placeholder inside this crate.
+    pub fn captured_location(&self) -> Option<&'static Location<'static>> {
+        if cfg!(feature = "no-location") {
+            None
+        } else {
+            Some(self.location)
+        }
+    }
 }
 
 impl Display for Layer {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match &self.context {
-            Some(context) => write!(f, "{} @ {}", context, self.location),
-            None => write!(f, "@ {}", self.location),
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match (&self.context, self.captured_location()) {
+            (Some(context), Some(location)) => write!(f, "{} @ {}", context, location),
+            (Some(context), None) => Display::fmt(context, f),
+            (None, Some(location)) => write!(f, "@ {}", location),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// The caller's location; with the `no-location` feature, the same
+/// placeholder for every caller so no call site paths end up in the binary.
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub(crate) fn caller() -> &'static Location<'static> {
+    Location::caller()
+}
+
+/// Index of the layer a report was created at, past any [`scope`] layers.
+pub(crate) fn origin_index(layers: &[Layer]) -> usize {
+    #[cfg(feature = "std")]
+    {
+        layers
+            .iter()
+            .position(|layer| !layer.is_scope())
+            .unwrap_or(0)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = layers;
+        0
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) enum Trace {
+    Captured(Backtrace),
+    #[cfg(feature = "anyhow")]
+    Rendered(String),
+}
+
+// Without `std` there is no backtrace to capture.
+#[cfg(not(feature = "std"))]
+pub(crate) enum Trace {}
+
+#[cfg(feature = "std")]
+impl Debug for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Trace::Captured(backtrace) => Debug::fmt(backtrace, f),
+            #[cfg(feature = "anyhow")]
+            Trace::Rendered(backtrace) => f.write_str(backtrace),
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Debug for Trace {
+    fn fmt(&self, _: &mut Formatter<'_>) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Trace::Captured(backtrace) => Display::fmt(backtrace, f),
+            #[cfg(feature = "anyhow")]
+            Trace::Rendered(backtrace) => f.write_str(backtrace),
         }
     }
 }
 
+#[cfg(not(feature = "std"))]
+impl Display for Trace {
+    fn fmt(&self, _: &mut Formatter<'_>) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+// One inline layer covers reports that are created and returned without further hops.
+#[cfg(not(feature = "compat-fields"))]
+type LayerStack = smallvec::SmallVec<[Layer; 1]>;
+#[cfg(feature = "compat-fields")]
+type LayerStack = Vec<Layer>;
+
+#[cfg(not(feature = "compat-fields"))]
+fn into_layer_vec(layers: LayerStack) -> Vec<Layer> {
+    layers.into_vec()
+}
+
+#[cfg(feature = "compat-fields")]
+fn into_layer_vec(layers: LayerStack) -> Vec<Layer> {
+    layers
+}
+
 pub struct Report<E>
 where
     E: ?Sized,
 {
+    #[cfg(feature = "compat-fields")]
+    #[deprecated(note = "use `Report::inner` or `Report::into_inner` instead")]
     pub inner: Box<E>,
+    #[cfg(not(feature = "compat-fields"))]
+    inner: Box<E>,
+    #[cfg(feature = "compat-fields")]
+    #[deprecated(note = "use `Report::layers` or `Report::push_layer` instead")]
     pub layers: Vec<Layer>,
+    #[cfg(not(feature = "compat-fields"))]
+    layers: LayerStack,
+    backtrace: Option<Box<Trace>>,
+    #[cfg(feature = "spantrace")]
+    span_trace: Option<Box<tracing_error::SpanTrace>>,
+    // Boxed so the rarely used cause costs one pointer in `Report`.
+    cause: Option<Box<Cause>>,
+    /// Type name of the error the report was created with, as a function
+    /// pointer to keep `Report` small.
+    error_type: fn() -> &'static str,
 }
 
+/// Through std's `From<E: Error>` impl, `?` also turns a report into a
+/// `Box<dyn Error + Send + Sync>` that still renders its layers; get the
+/// report back with `downcast_ref::<Report<E>>`.
 impl<E> Error for Report<E>
 where
     E: Error + ?Sized,
 {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.inner.source()
+        match &self.cause {
+            Some(cause) => Some(cause.error.as_ref()),
+            None => self.inner.source(),
+        }
+    }
+
+    #[cfg(feature = "unstable-provider")]
+    fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+        provider::provide(self, request);
     }
 }
 
@@ -40,10 +433,13 @@ impl<E> Debug for Report<E>
 where
     E: Debug + ?Sized,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Report")
-            .field("inner", &self.inner)
-            .finish()
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let inner = hook::DebugAsDisplay(&*self.inner);
+        let view = hook::ReportView::new(self, &inner);
+        match hook::handler() {
+            Some(handler) => handler.debug(&view, f),
+            None => view.write_default_debug(f),
+        }
     }
 }
 
@@ -51,14 +447,13 @@ impl<E> Display for Report<E>
 where
     E: Display + ?Sized,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let layer_string = self
-            .layers
-            .iter()
-            .map(|c| c.to_string())
-            .collect::<Vec<_>>()
-            .join(", ");
-        write!(f, "{}: {}", self.inner, layer_string)
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let inner = hook::Message(&*self.inner);
+        let view = hook::ReportView::new(self, &inner);
+        match hook::handler() {
+            Some(handler) => handler.display(&view, f),
+            None => view.write_default(f),
+        }
     }
 }
 
@@ -86,46 +481,489 @@ impl<E> Report<E>
 where
     E: ?Sized,
 {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     pub fn new(e: E) -> Self
     where
         E: Sized,
     {
+        Self::from_boxed(Box::new(e))
+    }
+
+    /// Like [`Report::new`], attributing the report to `location` instead of
+    /// the caller, for code that creates reports on behalf of other code.
+    pub fn new_at(e: E, location: &'static Location<'static>) -> Self
+    where
+        E: Sized,
+    {
+        Self::from_boxed_at(Box::new(e), location)
+    }
+
+    /// Moves the newest layer to `location`, e.g. one captured with
+    /// [`Location::caller`] in a `#[track_caller]` helper further up.
+    pub fn with_location(mut self, location: &'static Location<'static>) -> Self {
+        self.layers
+            .last_mut()
+            .expect("Report objects must have at least one layer")
+            .location = location;
+        self
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from_boxed(inner: Box<E>) -> Self {
+        Self::from_boxed_at(inner, caller())
+    }
+
+    #[cfg(feature = "std")]
+    fn from_boxed_at(inner: Box<E>, location: &'static Location<'static>) -> Self {
+        let backtrace = Backtrace::capture();
+        let backtrace = match backtrace.status() {
+            BacktraceStatus::Captured => Some(Box::new(Trace::Captured(backtrace))),
+            _ => None,
+        };
+        Self::from_boxed_with_trace(inner, backtrace, location)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn from_boxed_at(inner: Box<E>, location: &'static Location<'static>) -> Self {
+        Self::from_boxed_with_trace(inner, None, location)
+    }
+
+    fn from_boxed_with_trace(
+        inner: Box<E>,
+        backtrace: Option<Box<Trace>>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        #[cfg(feature = "tracing")]
+        instrument::report_created(core::any::type_name::<E>(), location);
+        sink::created(core::any::type_name::<E>(), location);
+        #[cfg(feature = "metrics")]
+        metric::created(core::any::type_name::<E>());
+        let mut origin = Layer::new(location);
+        if let Some(metadata) = global::collect() {
+            origin.attachments.push(Box::new(metadata));
+        }
+        #[cfg(feature = "uuid")]
+        origin.attachments.push(Box::new(id::ReportId::new()));
+        #[cfg(feature = "thread-info")]
+        origin
+            .attachments
+            .push(Box::new(thread::ThreadInfo::current()));
+        #[cfg(feature = "otel")]
+        if let Some(span_context) = otel::active_span_context() {
+            origin.attachments.push(Box::new(span_context));
+        }
+        #[cfg(feature = "std")]
+        let layers = {
+            let mut layers = LayerStack::new();
+            scope::extend(&mut layers);
+            layers.push(origin);
+            layers
+        };
+        #[cfg(not(feature = "std"))]
+        let layers = LayerStack::from_iter([origin]);
         Self {
-            inner: Box::new(e),
-            layers: vec![Layer {
-                context: None,
-                location: Location::caller(),
-            }],
+            inner,
+            layers,
+            backtrace,
+            #[cfg(feature = "spantrace")]
+            span_trace: {
+                let span_trace = tracing_error::SpanTrace::capture();
+                match span_trace.status() {
+                    tracing_error::SpanTraceStatus::CAPTURED => Some(Box::new(span_trace)),
+                    _ => None,
+                }
+            },
+            cause: None,
+            error_type: core::any::type_name::<E>,
+        }
+    }
+
+    /// Builds a report from an inner error and layers in push order, as
+    /// returned by [`Report::into_parts`]. The report is reassembled rather
+    /// than created: no sink, metric or tracing events fire and no backtrace
+    /// or scope layers are captured. Without layers, a bare layer at the
+    /// caller stands in as the origin.
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn from_parts(inner: Box<E>, layers: Vec<Layer>) -> Self {
+        let mut layers = LayerStack::from_iter(layers);
+        if layers.is_empty() {
+            layers.push(Layer::new(caller()));
+        }
+        Self {
+            inner,
+            layers,
+            backtrace: None,
+            #[cfg(feature = "spantrace")]
+            span_trace: None,
+            cause: None,
+            error_type: core::any::type_name::<E>,
+        }
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut E {
+        &mut self.inner
+    }
+
+    /// The layers in the order they were added, origin first. Use
+    /// [`Report::frames`] to walk them newest first.
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub fn push_layer(&mut self, layer: Layer) {
+        self.add_layer(layer);
+    }
+
+    /// The layer the report was created at. Layers from an enclosing
+    /// [`scope`] come before it in [`Report::layers`].
+    pub fn origin_layer(&self) -> &Layer {
+        &self.layers[origin_index(&self.layers)]
+    }
+
+    /// Where the report was created.
+    pub fn origin(&self) -> &'static Location<'static> {
+        self.origin_layer().location
+    }
+
+    /// Where the most recent layer was added.
+    pub fn latest_location(&self) -> &'static Location<'static> {
+        self.layers
+            .last()
+            .expect("Report objects must have at least one layer")
+            .location
+    }
+
+    pub fn into_inner(self) -> Box<E> {
+        self.inner
+    }
+
+    pub fn into_parts(self) -> (Box<E>, Vec<Layer>) {
+        (self.inner, into_layer_vec(self.layers))
+    }
+
+    fn add_layer(&mut self, layer: Layer) {
+        #[cfg(feature = "tracing")]
+        instrument::layer_added(layer.location);
+        if self.layers.len() == self.layers.capacity() {
+            // Skip the 1 -> 2 step once a report starts propagating.
+            self.layers.reserve(self.layers.len().max(3));
+        }
+        self.layers.push(layer);
+        self.merge_repeated();
+        if self.layers.len() > config::layer_cap() {
+            self.omit_layer();
+        }
+    }
+
+    fn map_boxed<E2, F>(self, f: F) -> Report<E2>
+    where
+        E2: ?Sized,
+        F: FnOnce(Box<E>) -> Box<E2>,
+    {
+        Report {
+            inner: f(self.inner),
+            layers: self.layers,
+            backtrace: self.backtrace,
+            #[cfg(feature = "spantrace")]
+            span_trace: self.span_trace,
+            cause: self.cause,
+            error_type: self.error_type,
         }
     }
 
+    fn into_any(self) -> Report<AnyError>
+    where
+        E: IntoAnyError,
+    {
+        #[cfg(feature = "metrics")]
+        metric::untyped::<E>(self.code());
+        self.map_boxed(IntoAnyError::into_any_error)
+    }
+
     pub fn into_untyped(self) -> Report<AnyError>
     where
         E: Error + Sync + Send + Sized + 'static,
     {
-        Report {
-            inner: self.inner,
+        #[cfg(feature = "metrics")]
+        metric::untyped::<E>(self.code());
+        self.map_boxed(|inner| inner as Box<AnyError>)
+    }
+
+    pub fn map_inner<F, E2>(self, f: F) -> Report<E2>
+    where
+        E: Sized,
+        F: FnOnce(E) -> E2,
+    {
+        self.map_boxed(|inner| Box::new(f(*inner)))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn map_into<E2>(self) -> Report<E2>
+    where
+        E: Into<E2> + Sized,
+    {
+        let mut report = self.map_inner(Into::into);
+        report.add_layer(Layer::new(caller()));
+        report
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn change_context<E2>(self, context: E2) -> Report<E2>
+    where
+        E: IntoAnyError,
+    {
+        let cause = self.cause;
+        let previous = self.inner.into_any_error();
+        let cause = match cause {
+            Some(cause) => Box::new(Caused::new(previous, cause.error)),
+            None => previous,
+        };
+        let mut report = Report {
+            inner: Box::new(context),
             layers: self.layers,
+            backtrace: self.backtrace,
+            #[cfg(feature = "spantrace")]
+            span_trace: self.span_trace,
+            cause: Some(Box::new(Cause::new(cause))),
+            error_type: self.error_type,
+        };
+        report.add_layer(Layer::new(caller()));
+        report
+    }
+
+    /// Starts a new report around `error` with this one as its source, for
+    /// outer error types that cannot hold a report themselves. Unlike
+    /// [`Report::change_context`], the layers stay with the wrapped report;
+    /// get it back with [`Report::wrapped`].
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn wrap_err<E2>(self, error: E2) -> Report<E2>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let mut report = Report::from_boxed_with_trace(Box::new(error), None, caller());
+        report.cause = Some(Box::new(Cause::new(Box::new(Wrapped(self)))));
+        report
+    }
+
+    /// The report of type `Report<T>` wrapped by [`Report::wrap_err`]
+    /// somewhere down the chain.
+    pub fn wrapped<T>(&self) -> Option<&Report<T>>
+    where
+        E: AsDynError,
+        T: Error + Send + Sync + 'static + ?Sized,
+    {
+        self.chain()
+            .find_map(|error| error.downcast_ref::<Wrapped<T>>())
+            .map(|wrapped| &wrapped.0)
+    }
+
+    /// Makes `source` the next error in the chain after the inner error,
+    /// ahead of the inner error's own source or an earlier cause.
+    ///
+    /// [`Report::chain`] walks on into the inner error's own source after
+    /// `source`; [`Error::source`] cannot, as that chain lives in the inner
+    /// error.
+    pub fn set_source<S>(&mut self, source: S)
+    where
+        S: Error + Send + Sync + 'static,
+    {
+        let source: Box<AnyError> = Box::new(source);
+        let cause = match self.cause.take() {
+            Some(cause) => Cause {
+                error: Box::new(Caused::new(source, cause.error)),
+                then_inner: cause.then_inner,
+            },
+            None => Cause {
+                error: source,
+                then_inner: true,
+            },
+        };
+        self.cause = Some(Box::new(cause));
+    }
+
+    /// [`Report::set_source`] by value. Named apart from
+    /// [`Report::with_source`], which attaches source code.
+    pub fn with_cause<S>(mut self, source: S) -> Report<E>
+    where
+        S: Error + Send + Sync + 'static,
+    {
+        self.set_source(source);
+        self
+    }
+
+    pub fn context<Ctx>(mut self, context: Ctx) -> Report<E>
+    where
+        Ctx: Display + Send + Sync + 'static,
+    {
+        let first_layer = self
+            .layers
+            .last_mut()
+            .expect("Report objects must have at least one layer");
+        if first_layer.context.is_some() && config::context_mode() == config::ContextMode::Append {
+            let mut layer = Layer::new(first_layer.location);
+            layer.context = Some(ContextValue::new(context));
+            self.add_layer(layer);
+        } else {
+            first_layer.context = Some(ContextValue::new(context));
+            self.merge_repeated();
         }
+        self
     }
 
-    pub fn context<Ctx>(self, context: Ctx) -> Report<E>
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn add_context<Ctx>(mut self, context: Ctx) -> Report<E>
     where
         Ctx: Display + Send + Sync + 'static,
     {
-        let mut layers = self.layers;
-        let first_layer = layers
-            .first_mut()
+        let mut layer = Layer::new(caller());
+        layer.context = Some(ContextValue::new(context));
+        self.add_layer(layer);
+        self
+    }
+
+    /// Calls `f` with the report and returns it unchanged.
+    pub fn tap<F>(self, f: F) -> Report<E>
+    where
+        F: FnOnce(&Report<E>),
+    {
+        f(&self);
+        self
+    }
+
+    pub fn attach<T>(mut self, value: T) -> Report<E>
+    where
+        T: Send + Sync + 'static,
+    {
+        let first_layer = self
+            .layers
+            .last_mut()
             .expect("Report objects must have at least one layer");
-        first_layer.context = Some(Box::new(context));
-        Report {
-            inner: self.inner,
-            layers,
+        first_layer.attachments.push(Box::new(value));
+        self
+    }
+
+    pub fn request_ref<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.attachments::<T>().next()
+    }
+
+    pub fn attachments<T>(&self) -> impl Iterator<Item = &T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.layers
+            .iter()
+            .rev()
+            .flat_map(|layer| layer.attachments.iter())
+            .filter_map(|attachment| attachment.downcast_ref::<T>())
+    }
+
+    pub fn metadata(&self) -> Option<&global::Metadata> {
+        self.request_ref()
+    }
+
+    pub fn frames(&self) -> impl DoubleEndedIterator<Item = &Layer> + ExactSizeIterator {
+        self.layers.iter().rev()
+    }
+
+    pub fn chain(&self) -> Chain<'_>
+    where
+        E: AsDynError,
+    {
+        let inner = self.inner.as_dyn_error();
+        let (next, tail) = self.chain_after(inner.source());
+        Chain::new(Some(inner), next, tail)
+    }
+
+    /// [`Report::chain`] without the inner error.
+    #[cfg(feature = "serde")]
+    pub(crate) fn sources(&self) -> Chain<'_>
+    where
+        E: Error,
+    {
+        let (next, tail) = self.chain_after(self.inner.source());
+        Chain::new(None, next, tail)
+    }
+
+    /// The chain after the inner error as far as it is known without
+    /// `E: Error`: an added cause and its sources.
+    pub(crate) fn added_causes(&self) -> Chain<'_> {
+        let (next, _) = self.chain_after(None);
+        Chain::new(None, next, None)
+    }
+
+    /// Where the chain goes after the inner error, given its own source.
+    fn chain_after<'a>(
+        &'a self,
+        inner_source: Option<&'a (dyn Error + 'static)>,
+    ) -> (
+        Option<&'a (dyn Error + 'static)>,
+        Option<&'a (dyn Error + 'static)>,
+    ) {
+        match &self.cause {
+            Some(cause) => (
+                Some(cause.error.as_ref()),
+                inner_source.filter(|_| cause.then_inner),
+            ),
+            None => (inner_source, None),
         }
     }
 
+    pub fn root_cause(&self) -> &(dyn Error + 'static)
+    where
+        E: AsDynError,
+    {
+        self.chain()
+            .last()
+            .expect("a report chain always contains the inner error")
+    }
+
+    pub fn find_cause<T>(&self) -> Option<&T>
+    where
+        E: AsDynError,
+        T: Error + 'static,
+    {
+        self.chain().find_map(|error| error.downcast_ref::<T>())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        match self.backtrace.as_deref()? {
+            Trace::Captured(backtrace) => Some(backtrace),
+            #[cfg(feature = "anyhow")]
+            Trace::Rendered(_) => None,
+        }
+    }
+
+    #[cfg(feature = "spantrace")]
+    pub fn span_trace(&self) -> Option<&tracing_error::SpanTrace> {
+        self.span_trace.as_deref()
+    }
+
+    pub fn write_to<W>(&self, writer: &mut W) -> core::fmt::Result
+    where
+        E: Display,
+        W: core::fmt::Write + ?Sized,
+    {
+        write!(writer, "{}", self)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_io<W>(&self, writer: &mut W) -> std::io::Result<()>
+    where
+        E: Display,
+        W: std::io::Write + ?Sized,
+    {
+        write!(writer, "{}", self)
+    }
+
     pub fn raw_message(&self) -> String
     where
         E: Display,
@@ -134,34 +972,101 @@ where
     }
 }
 
+impl Report<StringError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn msg(message: impl Into<String>) -> Self {
+        Self::new(StringError::new(message))
+    }
+}
+
+impl Report<AnyError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn from_display<T>(value: T) -> Self
+    where
+        T: Display + Send + Sync + 'static,
+    {
+        Self::from_boxed(Box::new(DisplayError(value)))
+    }
+
+    pub fn downcast<E>(self) -> Result<Report<E>, Self>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        if !self.inner.is::<E>() {
+            return Err(self);
+        }
+        Ok(self.map_boxed(|inner| {
+            inner
+                .downcast::<E>()
+                .expect("inner error was checked to be of the requested type")
+        }))
+    }
+
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.inner.downcast_ref::<E>()
+    }
+
+    pub fn downcast_mut<E>(&mut self) -> Option<&mut E>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.inner.downcast_mut::<E>()
+    }
+
+    pub fn is<E>(&self) -> bool
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        self.inner.is::<E>()
+    }
+}
+
 impl<E> From<E> for Report<E> {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn from(e: E) -> Self {
         Self::new(e)
     }
 }
 
+impl From<String> for Report<StringError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from(message: String) -> Self {
+        Self::msg(message)
+    }
+}
+
+impl From<&str> for Report<StringError> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn from(message: &str) -> Self {
+        Self::msg(message)
+    }
+}
+
 impl<E> From<Report<E>> for Report<AnyError>
 where
     E: Error + Sync + Send + 'static,
 {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     fn from(value: Report<E>) -> Self {
-        value.into_untyped()
+        let location = caller();
+        let mut report = value.into_untyped();
+        // `bail!` and `ensure!` convert where they create the report.
+        if report.latest_location() != location {
+            report.add_layer(Layer::new(location));
+        }
+        report
     }
 }
 
 impl From<Box<AnyError>> for Report<AnyError> {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn from(value: Box<AnyError>) -> Self {
-        Self {
-            inner: value,
-            layers: vec![Layer {
-                context: None,
-                location: Location::caller(),
-            }],
-        }
+        Self::from_boxed(value)
     }
 }
 
@@ -173,7 +1078,7 @@ where
 }
 
 impl<E> IntoReportExt<E> for E {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn into_report(self) -> Report<E> {
         Report::new(self)
@@ -181,16 +1086,10 @@ impl<E> IntoReportExt<E> for E {
 }
 
 impl IntoReportExt<AnyError> for Box<AnyError> {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn into_report(self) -> Report<AnyError> {
-        Report {
-            inner: self,
-            layers: vec![Layer {
-                context: None,
-                location: Location::caller(),
-            }],
-        }
+        Report::from_boxed(self)
     }
 }
 
@@ -203,10 +1102,16 @@ pub trait ResultIntoReportExt<T, E> {
     where
         Self: Sized,
         Ctx: Display + Sync + Send + 'static;
+
+    fn report_with<Ctx, F>(self, f: F) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx;
 }
 
 impl<T, E> ResultIntoReportExt<T, E> for Result<T, E> {
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn report(self) -> Result<T, Report<E>> {
         match self {
@@ -215,7 +1120,7 @@ impl<T, E> ResultIntoReportExt<T, E> for Result<T, E> {
         }
     }
 
-    #[track_caller]
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     #[inline]
     fn report_with_context<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
     where
@@ -227,6 +1132,20 @@ impl<T, E> ResultIntoReportExt<T, E> for Result<T, E> {
             Err(e) => Err(Report::new(e).context(context)),
         }
     }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    #[inline]
+    fn report_with<Ctx, F>(self, f: F) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx,
+    {
+        match self {
+            Ok(r) => Ok(r),
+            Err(e) => Err(Report::new(e).context(f())),
+        }
+    }
 }
 
 pub trait ResultReportExt<T, E> {
@@ -239,9 +1158,42 @@ pub trait ResultReportExt<T, E> {
         Self: Sized,
         Ctx: Display + Sync + Send + 'static;
 
+    fn with_context<Ctx, F>(self, f: F) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx;
+
+    fn add_context<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static;
+
+    fn context_sensitive<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static;
+
+    fn attach<A>(self, value: A) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        A: Send + Sync + 'static;
+
     fn layer(self) -> Result<T, Report<E>>
     where
         Self: Sized;
+
+    fn err_into<E2>(self) -> Result<T, Report<E2>>
+    where
+        Self: Sized,
+        E: Into<E2>;
+
+    /// Calls `f` with the report on the error path, e.g. to log or count it,
+    /// and returns the result unchanged.
+    fn inspect_err_report<F>(self, f: F) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        F: FnOnce(&Report<E>);
 }
 
 impl<T, E> ResultReportExt<T, E> for Result<T, Report<E>>
@@ -249,8 +1201,7 @@ where
     E: Error + Send + Sync + 'static,
 {
     fn untyped_err(self) -> Result<T, Report<AnyError>> {
-        let res = self.map_err(|e| e.into_untyped());
-        res
+        self.map_err(|e| e.into_untyped())
     }
 
     fn context<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
@@ -261,21 +1212,166 @@ where
         self.map_err(|e| e.context(context))
     }
 
+    fn with_context<Ctx, F>(self, f: F) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx,
+    {
+        self.map_err(|e| e.context(f()))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn add_context<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+    {
+        match self {
+            Ok(r) => Ok(r),
+            Err(e) => Err(e.add_context(context)),
+        }
+    }
+
+    fn context_sensitive<Ctx>(self, context: Ctx) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+    {
+        self.map_err(|e| e.context_sensitive(context))
+    }
+
+    fn attach<A>(self, value: A) -> Result<T, Report<E>>
+    where
+        Self: Sized,
+        A: Send + Sync + 'static,
+    {
+        self.map_err(|e| e.attach(value))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
     fn layer(self) -> Result<T, Report<E>> {
         match self {
             Ok(r) => Ok(r),
-            Err(e) => {
-                let new_context = Layer {
-                    context: None,
-                    location: Location::caller(),
-                };
-                let mut layers = e.layers;
-                layers.insert(0, new_context);
-                Err(Report {
-                    inner: e.inner,
-                    layers,
-                })
+            Err(mut e) => {
+                e.add_layer(Layer::new(caller()));
+                Err(e)
             }
         }
     }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn err_into<E2>(self) -> Result<T, Report<E2>>
+    where
+        E: Into<E2>,
+    {
+        match self {
+            Ok(r) => Ok(r),
+            Err(e) => Err(e.map_into()),
+        }
+    }
+
+    fn inspect_err_report<F>(self, f: F) -> Result<T, Report<E>>
+    where
+        F: FnOnce(&Report<E>),
+    {
+        self.map_err(|e| e.tap(f))
+    }
+}
+
+pub trait OptionIntoReportExt<T> {
+    fn report(self) -> Result<T, Report<NoneError>>
+    where
+        Self: Sized;
+
+    fn ok_or_report<Ctx>(self, context: Ctx) -> Result<T, Report<NoneError>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static;
+
+    fn ok_or_report_with<Ctx, F>(self, f: F) -> Result<T, Report<NoneError>>
+    where
+        Self: Sized,
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx;
+}
+
+impl<T> OptionIntoReportExt<T> for Option<T> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    #[inline]
+    fn report(self) -> Result<T, Report<NoneError>> {
+        match self {
+            Some(r) => Ok(r),
+            None => Err(Report::new(NoneError)),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    #[inline]
+    fn ok_or_report<Ctx>(self, context: Ctx) -> Result<T, Report<NoneError>>
+    where
+        Ctx: Display + Sync + Send + 'static,
+    {
+        match self {
+            Some(r) => Ok(r),
+            None => Err(Report::new(NoneError).context(context)),
+        }
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    #[inline]
+    fn ok_or_report_with<Ctx, F>(self, f: F) -> Result<T, Report<NoneError>>
+    where
+        Ctx: Display + Sync + Send + 'static,
+        F: FnOnce() -> Ctx,
+    {
+        match self {
+            Some(r) => Ok(r),
+            None => Err(Report::new(NoneError).context(f())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_names_the_latest_layer_and_add_context_pushes_one() {
+        let report = Report::msg("boom").context("reading");
+        assert_eq!(report.layers().len(), 1);
+        assert_eq!(report.layers()[0].context_str().as_deref(), Some("reading"));
+        let report = report.add_context("retrying");
+        let contexts = report.frames().map(Layer::context_str).collect::<Vec<_>>();
+        assert_eq!(contexts, [Some("retrying".into()), Some("reading".into())]);
+    }
+
+    #[test]
+    fn parts_round_trip() {
+        let report = Report::msg("boom").add_context("a").add_context("b");
+        let origin = report.origin();
+        let latest = report.latest_location();
+        let (inner, layers) = report.into_parts();
+        let report = Report::from_parts(inner, layers);
+        assert_eq!(report.layers().len(), 3);
+        assert_eq!(report.origin(), origin);
+        assert_eq!(report.latest_location(), latest);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_parts_captures_nothing() {
+        let (inner, layers) = Report::msg("boom").into_parts();
+        let report = scope::in_scope("loading", || Report::from_parts(inner, layers));
+        assert_eq!(report.layers().len(), 1);
+        assert!(report.backtrace().is_none());
+    }
+
+    #[test]
+    fn untyped_reports_downcast_to_the_original_error() {
+        let report = Report::msg("boom").into_untyped();
+        assert!(report.is::<StringError>());
+        let report = report.downcast::<StringError>().expect("is a StringError");
+        assert_eq!(report.inner().as_str(), "boom");
+    }
 }
@@ -0,0 +1,82 @@
+use crate::Report;
+use log::{Level, Record};
+use std::fmt::Display;
+use std::panic::Location;
+
+/// Approximates a module path from a source file, e.g. `src/net/client.rs`
+/// becomes `net::client`. Crate roots fall back to the file path.
+fn target(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    let path = file
+        .rsplit_once("src/")
+        .map_or(file.as_str(), |(_, path)| path);
+    let path = path.strip_suffix(".rs").unwrap_or(path);
+    let mut segments = path.split('/').collect::<Vec<_>>();
+    if matches!(segments.last(), Some(&("mod" | "lib" | "main"))) {
+        segments.pop();
+    }
+    if segments.is_empty() {
+        return file;
+    }
+    segments.join("::")
+}
+
+impl<E> Report<E>
+where
+    E: Display + ?Sized,
+{
+    /// Emits the pretty rendering through the `log` facade, attributed to
+    /// the origin location.
+    pub fn log(&self, level: Level) {
+        let origin = self.origin_layer().captured_location();
+        let target = origin.map_or_else(
+            || env!("CARGO_CRATE_NAME").to_string(),
+            |origin| target(origin.file()),
+        );
+        if !log::log_enabled!(target: &target, level) {
+            return;
+        }
+        log::logger().log(
+            &Record::builder()
+                .args(format_args!("{:#}", self))
+                .level(level)
+                .target(&target)
+                .file(origin.map(Location::file))
+                .line(origin.map(Location::line))
+                .build(),
+        );
+    }
+}
+
+pub trait LogResultExt<T, E>
+where
+    E: ?Sized,
+{
+    /// Logs the error at [`Level::Error`] and returns the result unchanged.
+    fn log_err(self) -> Self;
+}
+
+impl<T, E> LogResultExt<T, E> for Result<T, Report<E>>
+where
+    E: Display + ?Sized,
+{
+    fn log_err(self) -> Self {
+        if let Err(report) = &self {
+            report.log(Level::Error);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_follows_the_module_path() {
+        assert_eq!(target("src/net/client.rs"), "net::client");
+        assert_eq!(target("crates/app/src/net/mod.rs"), "net");
+        assert_eq!(target("src\\db\\pool.rs"), "db::pool");
+        assert_eq!(target("src/main.rs"), "src/main.rs");
+    }
+}
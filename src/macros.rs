@@ -0,0 +1,68 @@
+#[macro_export]
+macro_rules! report {
+    ($($arg:tt)+) => {
+        $crate::Report::new($crate::StringError::new($crate::__private::format!($($arg)+)))
+    };
+}
+
+#[macro_export]
+macro_rules! bail {
+    ($($arg:tt)+) => {
+        return ::core::result::Result::Err(::core::convert::From::from($crate::report!($($arg)+)))
+    };
+}
+
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            return ::core::result::Result::Err(::core::convert::From::from(
+                $crate::report!("condition failed")
+                    .context(::core::concat!("`", ::core::stringify!($cond), "`")),
+            ));
+        }
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return ::core::result::Result::Err(::core::convert::From::from(
+                $crate::report!($($arg)+).context(::core::concat!(
+                    "condition failed: `",
+                    ::core::stringify!($cond),
+                    "`"
+                )),
+            ));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AnyError, Report};
+
+    fn positive(x: i32) -> Result<i32, Report<AnyError>> {
+        crate::ensure!(x > 0);
+        Ok(x)
+    }
+
+    fn fail(x: i32) -> Result<i32, Report<AnyError>> {
+        crate::bail!("failed with {}", x)
+    }
+
+    #[test]
+    fn bail_and_ensure_add_no_conversion_layer() {
+        let report = positive(0).unwrap_err();
+        assert_eq!(report.layers().len(), 1);
+        assert_eq!(fail(2).unwrap_err().layers().len(), 1);
+    }
+
+    #[test]
+    fn question_mark_still_adds_a_layer() {
+        fn convert() -> Result<(), Report<AnyError>> {
+            Err(Report::msg("typed"))?;
+            Ok(())
+        }
+        let report = convert().unwrap_err();
+        let expected = if cfg!(feature = "no-location") { 1 } else { 2 };
+        assert_eq!(report.layers().len(), expected);
+    }
+}
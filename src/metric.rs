@@ -0,0 +1,92 @@
+use crate::AnyError;
+use crate::sync::RwLock;
+use core::any::TypeId;
+
+static COUNTER: RwLock<&'static str> = RwLock::new("err_report_reports_total");
+
+/// Renames the counter incremented on every report creation and untyped
+/// conversion, `err_report_reports_total` by default. It is labelled with
+/// `event` (`created` or `untyped`), `error_type` and, once set, `code`.
+pub fn set_counter_name(name: &'static str) {
+    *COUNTER.write() = name;
+}
+
+pub fn counter_name() -> &'static str {
+    *COUNTER.read()
+}
+
+pub(crate) fn created(error_type: &'static str) {
+    metrics::counter!(counter_name(), "event" => "created", "error_type" => error_type)
+        .increment(1);
+}
+
+/// Counts a conversion from `Report<E>` to `Report<AnyError>`; reports that
+/// were untyped already are not counted again.
+pub(crate) fn untyped<E>(code: Option<&'static str>)
+where
+    E: ?Sized + 'static,
+{
+    if TypeId::of::<E>() == TypeId::of::<AnyError>() {
+        return;
+    }
+    let error_type = core::any::type_name::<E>();
+    match code {
+        Some(code) => metrics::counter!(
+            counter_name(),
+            "event" => "untyped",
+            "error_type" => error_type,
+            "code" => code
+        ),
+        None => metrics::counter!(counter_name(), "event" => "untyped", "error_type" => error_type),
+    }
+    .increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Report, ReportGroup};
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct Untyped(Arc<AtomicU64>);
+
+    impl Recorder for Untyped {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let untyped = key
+                .labels()
+                .any(|label| label.key() == "event" && label.value() == "untyped");
+            if untyped {
+                Counter::from_arc(self.0.clone())
+            } else {
+                Counter::noop()
+            }
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn untyped_reports_are_counted_once() {
+        let recorder = Untyped::default();
+        metrics::with_local_recorder(&recorder, || {
+            let mut group = ReportGroup::new();
+            group.push(Report::msg("typed"));
+            group.push(Report::msg("untyped").into_untyped());
+        });
+        assert_eq!(recorder.0.load(Ordering::Relaxed), 2);
+    }
+}
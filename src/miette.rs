@@ -0,0 +1,78 @@
+use crate::{NamedSource, Report};
+use miette::{
+    Diagnostic, LabeledSpan, MietteError, MietteSpanContents, Severity, SourceCode, SourceSpan,
+    SpanContents,
+};
+use std::error::Error;
+use std::fmt::Display;
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_severity(self, severity: Severity) -> Report<E> {
+        self.attach(severity)
+    }
+
+    pub fn severity(&self) -> Option<Severity> {
+        self.request_ref::<Severity>().copied()
+    }
+}
+
+impl SourceCode for NamedSource {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        let contents = self
+            .text()
+            .read_span(span, context_lines_before, context_lines_after)?;
+        Ok(Box::new(MietteSpanContents::new_named(
+            self.name().to_string(),
+            contents.data(),
+            *contents.span(),
+            contents.line(),
+            contents.column(),
+            contents.line_count(),
+        )))
+    }
+}
+
+impl<E> Diagnostic for Report<E>
+where
+    E: Error + ?Sized,
+{
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Report::code(self).map(|code| Box::new(code) as Box<dyn Display>)
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        Report::severity(self)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = self.suggestions().collect::<Vec<_>>().join("\n");
+        (!help.is_empty()).then(|| Box::new(help) as Box<dyn Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Report::source_code(self).map(|source| source as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let mut labels = self.labels().peekable();
+        labels.peek()?;
+        Some(Box::new(labels.map(|label| {
+            let span = label.span();
+            LabeledSpan::new(Some(label.message().to_string()), span.start, span.len())
+        })))
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.help_urls()
+            .next()
+            .map(|url| Box::new(url) as Box<dyn Display>)
+    }
+}
@@ -0,0 +1,20 @@
+use crate::Report;
+use std::fmt::Display;
+
+/// The message is the pretty rendering. Each layer becomes a `cause`, newest
+/// first, so JavaScript can walk `error.cause.cause…` down to the origin.
+impl<E> From<Report<E>> for napi::Error
+where
+    E: Display + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        report.finalize();
+        let mut error = napi::Error::from_reason(format!("{:#}", report));
+        error.cause = report.layers().iter().fold(None, |cause, layer| {
+            let mut error = napi::Error::from_reason(layer.to_string());
+            error.cause = cause;
+            Some(Box::new(error))
+        });
+        error
+    }
+}
@@ -0,0 +1,64 @@
+use crate::Report;
+use opentelemetry::trace::{SpanContext, SpanRef, Status, TraceContextExt};
+use opentelemetry::{Context, KeyValue};
+use std::fmt::Display;
+
+pub(crate) fn active_span_context() -> Option<SpanContext> {
+    let context = Context::current();
+    let span_context = context.span().span_context().clone();
+    span_context.is_valid().then_some(span_context)
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// The span that was active when the report was created.
+    pub fn span_context(&self) -> Option<&SpanContext> {
+        self.request_ref()
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Display + ?Sized,
+{
+    /// Records the report as an `exception` event on `span` and marks the
+    /// span as failed.
+    pub fn record_on_span(&self, span: &SpanRef<'_>) {
+        span.add_event("exception", self.exception_attributes());
+        span.set_status(Status::error(self.inner.to_string()));
+    }
+
+    /// The semantic convention attributes of an `exception` event, typed by
+    /// the error the report was created with.
+    fn exception_attributes(&self) -> Vec<KeyValue> {
+        let stack = self
+            .frames()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        vec![
+            KeyValue::new("exception.type", (self.error_type)()),
+            KeyValue::new("exception.message", self.inner.to_string()),
+            KeyValue::new("exception.stacktrace", stack),
+        ]
+    }
+
+    pub fn record_on_current_span(&self) {
+        self.record_on_span(&Context::current().span());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Report;
+
+    #[test]
+    fn exception_type_is_the_original_error_type() {
+        let report = Report::msg("boom").into_untyped();
+        let attributes = report.exception_attributes();
+        assert_eq!(attributes[0].key.as_str(), "exception.type");
+        assert_eq!(attributes[0].value.as_str(), "err_report::StringError");
+    }
+}
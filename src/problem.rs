@@ -0,0 +1,74 @@
+use crate::{AsDynError, Report, codes, config, status};
+use serde_json::{Map, Value, json};
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProblemInstance(pub String);
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_instance(self, instance: impl Into<String>) -> Report<E> {
+        self.attach(ProblemInstance(instance.into()))
+    }
+
+    /// An RFC 9457 problem document. `title` is the status's reason phrase;
+    /// a registered code's explanation goes in an `explanation` member.
+    pub fn to_problem_json(&self) -> Value
+    where
+        E: AsDynError + Debug + Display,
+    {
+        let status = self.status().unwrap_or(500);
+        let mut problem = Map::new();
+        problem.insert(
+            "type".into(),
+            json!(self.help_urls().next().unwrap_or("about:blank")),
+        );
+        if let Some(title) = status::reason_phrase(status) {
+            problem.insert("title".into(), json!(title));
+        }
+        problem.insert("status".into(), json!(status));
+        let detail = if config::expose_details() {
+            self.to_string()
+        } else {
+            self.inner.to_string()
+        };
+        problem.insert("detail".into(), json!(detail));
+        if let Some(ProblemInstance(instance)) = self.request_ref::<ProblemInstance>() {
+            problem.insert("instance".into(), json!(instance));
+        }
+        if let Some(code) = self.code() {
+            problem.entry("code").or_insert(json!(code));
+            if let Some(explanation) = codes::explain(code) {
+                problem.entry("explanation").or_insert(json!(explanation));
+            }
+        }
+        if let Some(metadata) = self.metadata() {
+            for (key, value) in metadata.iter() {
+                problem.entry(key).or_insert(json!(value));
+            }
+        }
+        Value::Object(problem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Report, codes};
+    use serde_json::json;
+
+    #[test]
+    fn title_is_the_reason_phrase() {
+        codes::register("E_PROBLEM_TITLE", "The widget could not be found anywhere.");
+        let problem = Report::msg("no widget")
+            .with_code("E_PROBLEM_TITLE")
+            .with_status(404)
+            .to_problem_json();
+        assert_eq!(problem["title"], json!("Not Found"));
+        assert_eq!(
+            problem["explanation"],
+            json!("The widget could not be found anywhere.")
+        );
+    }
+}
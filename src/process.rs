@@ -0,0 +1,161 @@
+use crate::{Report, caller};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::panic::Location;
+use std::process::{Child, Command, ExitStatus, Output};
+
+#[derive(Debug)]
+pub enum CommandError {
+    /// The process could not be started or waited on.
+    Io(io::Error),
+    /// The process ran but did not exit successfully.
+    Failed(ExitStatus),
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandError::Io(e) => Display::fmt(e, f),
+            CommandError::Failed(status) => write!(f, "process failed with {}", status),
+        }
+    }
+}
+
+impl Error for CommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CommandError::Io(e) => Some(e),
+            CommandError::Failed(_) => None,
+        }
+    }
+}
+
+/// The command a [`CommandError`] came from, attached by
+/// [`CommandReportExt`] and used as the layer context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandContext {
+    program: String,
+    args: Vec<String>,
+    status: Option<ExitStatus>,
+    stderr: Option<String>,
+}
+
+impl CommandContext {
+    fn new(command: &Command) -> Self {
+        Self {
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            status: None,
+            stderr: None,
+        }
+    }
+
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    pub fn status(&self) -> Option<ExitStatus> {
+        self.status
+    }
+
+    pub fn stderr(&self) -> Option<&str> {
+        self.stderr.as_deref()
+    }
+}
+
+impl Display for CommandContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "running `{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {}", arg)?;
+        }
+        f.write_str("`")?;
+        if let Some(stderr) = self.stderr.as_deref().map(str::trim)
+            && !stderr.is_empty()
+        {
+            write!(f, " (stderr: {})", stderr)?;
+        }
+        Ok(())
+    }
+}
+
+fn report(
+    error: CommandError,
+    context: CommandContext,
+    location: &'static Location<'static>,
+) -> Report<CommandError> {
+    Report::from_boxed_at(Box::new(error), location)
+        .context(context.clone())
+        .attach(context)
+}
+
+pub trait CommandReportExt {
+    /// Like [`Command::output`], but a non-zero exit is an error too.
+    fn report_output(&mut self) -> Result<Output, Report<CommandError>>;
+
+    /// Like [`Command::status`], but a non-zero exit is an error too.
+    fn report_status(&mut self) -> Result<ExitStatus, Report<CommandError>>;
+
+    fn report_spawn(&mut self) -> Result<Child, Report<CommandError>>;
+}
+
+impl CommandReportExt for Command {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_output(&mut self) -> Result<Output, Report<CommandError>> {
+        let location = caller();
+        let mut context = CommandContext::new(self);
+        let output = match self.output() {
+            Ok(output) => output,
+            Err(e) => return Err(report(CommandError::Io(e), context, location)),
+        };
+        if output.status.success() {
+            return Ok(output);
+        }
+        context.status = Some(output.status);
+        context.stderr = Some(String::from_utf8_lossy(&output.stderr).into_owned());
+        Err(report(
+            CommandError::Failed(output.status),
+            context,
+            location,
+        ))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_status(&mut self) -> Result<ExitStatus, Report<CommandError>> {
+        let location = caller();
+        let mut context = CommandContext::new(self);
+        let status = match self.status() {
+            Ok(status) => status,
+            Err(e) => return Err(report(CommandError::Io(e), context, location)),
+        };
+        if status.success() {
+            return Ok(status);
+        }
+        context.status = Some(status);
+        Err(report(CommandError::Failed(status), context, location))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_spawn(&mut self) -> Result<Child, Report<CommandError>> {
+        let location = caller();
+        self.spawn()
+            .map_err(|e| report(CommandError::Io(e), CommandContext::new(self), location))
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn command(&self) -> Option<&CommandContext> {
+        self.request_ref()
+    }
+}
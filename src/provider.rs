@@ -0,0 +1,35 @@
+use crate::codes::ErrorCode;
+use crate::global::Metadata;
+use crate::{HttpStatus, Layer, Report};
+use std::backtrace::Backtrace;
+use std::error::{Error, Request};
+use std::panic::Location;
+
+/// Answers [`std::error::request_ref`] for a report: the backtrace, the
+/// layers, the newest location and well-known attachments, then whatever
+/// the inner error provides.
+pub(crate) fn provide<'a, E>(report: &'a Report<E>, request: &mut Request<'a>)
+where
+    E: Error + ?Sized,
+{
+    if let Some(backtrace) = report.backtrace() {
+        request.provide_ref::<Backtrace>(backtrace);
+    }
+    #[cfg(feature = "spantrace")]
+    if let Some(span_trace) = report.span_trace() {
+        request.provide_ref::<tracing_error::SpanTrace>(span_trace);
+    }
+    request
+        .provide_ref::<[Layer]>(report.layers())
+        .provide_ref::<Location<'static>>(report.latest_location());
+    if let Some(code) = report.request_ref::<ErrorCode>() {
+        request.provide_ref::<ErrorCode>(code);
+    }
+    if let Some(status) = report.request_ref::<HttpStatus>() {
+        request.provide_ref::<HttpStatus>(status);
+    }
+    if let Some(metadata) = report.request_ref::<Metadata>() {
+        request.provide_ref::<Metadata>(metadata);
+    }
+    report.inner().provide(request);
+}
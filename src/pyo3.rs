@@ -0,0 +1,43 @@
+use crate::Report;
+use pyo3::exceptions::PyException;
+use pyo3::types::PyAnyMethods;
+use pyo3::{PyErr, Python};
+use std::fmt::Display;
+
+pyo3::create_exception!(
+    err_report,
+    ReportError,
+    PyException,
+    "Raised for a Rust report. The argument is the rendered report; `message` is the inner error's message and `layers` a list of `(context, file, line)` tuples, newest first."
+);
+
+impl<E> From<Report<E>> for PyErr
+where
+    E: Display + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        report.finalize();
+        let layers = report
+            .frames()
+            .map(|layer| {
+                let location = layer.captured_location();
+                (
+                    layer.context_str(),
+                    location.map_or("", |location| location.file()),
+                    location.map_or(0, |location| location.line()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let error = ReportError::new_err(format!("{:#}", report));
+        Python::attach(|py| {
+            let value = error.value(py);
+            match value
+                .setattr("message", report.inner().to_string())
+                .and_then(|()| value.setattr("layers", layers))
+            {
+                Ok(()) => error,
+                Err(e) => e,
+            }
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{ContextValue, IntoAnyError, Layer, Report, ReportGroup, caller};
+use rayon::iter::IndexedParallelIterator;
+
+pub trait ParallelCollectReportsExt<T> {
+    /// Runs the whole iterator and returns every value, or a group of every
+    /// failure in index order, each with an `item N` layer.
+    fn collect_reports(self) -> Result<Vec<T>, ReportGroup>
+    where
+        Self: Sized;
+}
+
+impl<I, T, E> ParallelCollectReportsExt<T> for I
+where
+    I: IndexedParallelIterator<Item = Result<T, Report<E>>>,
+    T: Send,
+    E: IntoAnyError + Send + ?Sized,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn collect_reports(self) -> Result<Vec<T>, ReportGroup> {
+        let location = caller();
+        let items: Vec<_> = self.collect();
+        let mut values = Vec::with_capacity(items.len());
+        let mut group = ReportGroup::new();
+        for (index, item) in items.into_iter().enumerate() {
+            match item {
+                Ok(value) => values.push(value),
+                Err(mut report) => {
+                    let mut layer = Layer::new(location);
+                    layer.context = Some(ContextValue::from(format!("item {}", index)));
+                    report.push_layer(layer);
+                    group.push(report);
+                }
+            }
+        }
+        if group.is_empty() {
+            Ok(values)
+        } else {
+            Err(group)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Report;
+use crate::config::{self, RedactionMode};
+use alloc::sync::Arc;
+use core::fmt::{Debug, Display, Formatter};
+
+const REDACTED: &str = "[REDACTED]";
+
+/// A value rendered as `[REDACTED]` in `Display`, `Debug` and serialized
+/// output unless [`RedactionMode::Reveal`] is installed. The value itself
+/// stays available through [`Redacted::expose`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Redacted<T>(T);
+
+impl<T> Redacted<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Redacted<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Display for Redacted<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match config::redaction_mode() {
+            RedactionMode::Redact => f.write_str(REDACTED),
+            RedactionMode::Reveal => Display::fmt(&self.0, f),
+        }
+    }
+}
+
+impl<T> Debug for Redacted<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match config::redaction_mode() {
+            RedactionMode::Redact => f.debug_tuple("Redacted").field(&REDACTED).finish(),
+            RedactionMode::Reveal => f.debug_tuple("Redacted").field(&self.0).finish(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Redacted<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match config::redaction_mode() {
+            RedactionMode::Redact => serializer.serialize_str(REDACTED),
+            RedactionMode::Reveal => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// Sets a context that renders as `[REDACTED]`. The value is also
+    /// attached and can be read back with [`Report::sensitive`].
+    pub fn context_sensitive<Ctx>(self, context: Ctx) -> Report<E>
+    where
+        Ctx: Display + Send + Sync + 'static,
+    {
+        let context = Redacted(Arc::new(context));
+        self.context(context.clone()).attach(context)
+    }
+
+    /// The most recent value given to [`Report::context_sensitive`] with
+    /// type `T`, regardless of the redaction mode.
+    pub fn sensitive<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.request_ref::<Redacted<Arc<T>>>()
+            .map(|redacted| &**redacted.expose())
+    }
+}
@@ -0,0 +1,586 @@
+use crate::codes::ErrorCode;
+use crate::config::{Config, LocationStyle, Order, Verbosity};
+use crate::global::Metadata;
+use crate::{HelpUrl, Label, Layer, NamedSource, ReportView, Suggestion, hook};
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::panic::Location;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy)]
+struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    fn new(config: &Config) -> Self {
+        #[cfg(feature = "color")]
+        let enabled = crate::config::use_color(config);
+        #[cfg(not(feature = "color"))]
+        let enabled = {
+            let _ = config;
+            false
+        };
+        Self { enabled }
+    }
+
+    fn paint(self, f: &mut Formatter<'_>, style: &str, value: impl Display) -> core::fmt::Result {
+        if self.enabled {
+            write!(f, "\x1b[{}m{}\x1b[0m", style, value)
+        } else {
+            write!(f, "{}", value)
+        }
+    }
+
+    fn error(self, f: &mut Formatter<'_>, value: impl Display) -> core::fmt::Result {
+        self.paint(f, "31", value)
+    }
+
+    fn context(self, f: &mut Formatter<'_>, value: impl Display) -> core::fmt::Result {
+        self.paint(f, "33", value)
+    }
+
+    fn help(self, f: &mut Formatter<'_>, value: impl Display) -> core::fmt::Result {
+        self.paint(f, "36", value)
+    }
+
+    fn location(self, f: &mut Formatter<'_>, value: impl Display) -> core::fmt::Result {
+        self.paint(f, "2", value)
+    }
+}
+
+pub(crate) struct LocationDisplay {
+    location: &'static Location<'static>,
+    file: &'static str,
+    stable: bool,
+}
+
+fn is_absolute(file: &str) -> bool {
+    let bytes = file.as_bytes();
+    file.starts_with(['/', '\\'])
+        || (bytes.len() > 2 && bytes[1] == b':' && matches!(bytes[2], b'/' | b'\\'))
+}
+
+impl Display for LocationDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if !self.stable {
+            return write!(
+                f,
+                "{}:{}:{}",
+                self.file,
+                self.location.line(),
+                self.location.column()
+            );
+        }
+        if is_absolute(self.file) {
+            let name = self.file.rsplit(['/', '\\']).next().unwrap_or(self.file);
+            write!(f, "$DIR/{}:LL:CC", name)
+        } else {
+            write!(f, "{}:LL:CC", self.file)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn current_dir() -> Option<&'static str> {
+    static CURRENT_DIR: OnceLock<Option<String>> = OnceLock::new();
+    CURRENT_DIR
+        .get_or_init(|| {
+            let dir = std::env::current_dir().ok()?;
+            dir.to_str().map(str::to_string)
+        })
+        .as_deref()
+}
+
+#[cfg(not(feature = "std"))]
+fn current_dir() -> Option<&'static str> {
+    None
+}
+
+fn strip_dir<'a>(file: &'a str, dir: &str) -> Option<&'a str> {
+    let rest = file.strip_prefix(dir)?;
+    if dir.ends_with(['/', '\\']) {
+        return Some(rest);
+    }
+    rest.strip_prefix(['/', '\\'])
+}
+
+/// The file path of a location as configured for display and serialization.
+pub(crate) fn file(location: &'static Location<'static>, config: &Config) -> &'static str {
+    let mut file = location.file();
+    if let Some(rest) = config
+        .strip_prefix()
+        .and_then(|prefix| file.strip_prefix(prefix))
+    {
+        file = rest;
+    }
+    match config.location_style() {
+        LocationStyle::Full => file,
+        LocationStyle::FileNameOnly => file.rsplit(['/', '\\']).next().unwrap_or(file),
+        LocationStyle::Relative => current_dir()
+            .and_then(|dir| strip_dir(file, dir))
+            .unwrap_or(file),
+    }
+}
+
+/// Formats as `@ file:line:col`, followed by ` (xN)` for repeated layers, or
+/// as nothing with the `no-location` feature.
+pub(crate) struct At(Option<LocationDisplay>, usize);
+
+impl At {
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl Display for At {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let Some(location) = &self.0 else {
+            return Ok(());
+        };
+        write!(f, "@ {}", location)?;
+        if self.1 > 1 {
+            write!(f, " (x{})", self.1)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn location(location: &'static Location<'static>, config: &Config) -> LocationDisplay {
+    LocationDisplay {
+        location,
+        file: file(location, config),
+        stable: config.stable_output(),
+    }
+}
+
+fn at(layer: &Layer, repeats: usize, config: &Config) -> At {
+    if layer.omitted().is_some() {
+        return At(None, 1);
+    }
+    let location = layer
+        .captured_location()
+        .map(|captured| location(captured, config));
+    At(location, repeats)
+}
+
+/// Layers in display order, with runs of identical layers collapsed into the
+/// first of the run and a repeat count as they are walked.
+#[derive(Clone)]
+struct Groups<'a> {
+    layers: &'a [Layer],
+    root_first: bool,
+}
+
+impl<'a> Groups<'a> {
+    fn peek(&self) -> Option<&'a Layer> {
+        if self.root_first {
+            self.layers.first()
+        } else {
+            self.layers.last()
+        }
+    }
+
+    fn pop(&mut self) -> Option<&'a Layer> {
+        let (layer, rest) = if self.root_first {
+            self.layers.split_first()?
+        } else {
+            self.layers.split_last()?
+        };
+        self.layers = rest;
+        Some(layer)
+    }
+}
+
+impl<'a> Iterator for Groups<'a> {
+    type Item = (&'a Layer, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let layer = self.pop()?;
+        let mut repeats = layer.repeats();
+        while let Some(next) = self.peek().filter(|next| layer.same_as(next)) {
+            repeats += next.repeats();
+            self.pop();
+        }
+        Some((layer, repeats))
+    }
+}
+
+/// Returns the layers to show, in display order, with runs of identical
+/// layers collapsed into one and a repeat count, and how many were left out.
+fn shown_layers<'a>(
+    report: &ReportView<'a>,
+    config: &Config,
+) -> (
+    impl Iterator<Item = (&'a Layer, usize)> + Clone + use<'a>,
+    usize,
+) {
+    let groups = Groups {
+        layers: report.layers(),
+        root_first: config.order() == Order::RootFirst,
+    };
+    let Some(max) = config.max_layers_shown() else {
+        return (groups.take(usize::MAX), 0);
+    };
+    let total = groups.clone().count();
+    let shown = max.min(total);
+    (groups.take(shown), total - shown)
+}
+
+struct CharCounter(usize);
+
+impl core::fmt::Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+fn width(value: &dyn Display) -> usize {
+    use core::fmt::Write;
+
+    let mut counter = CharCounter(0);
+    let _ = write!(counter, "{}", value);
+    counter.0
+}
+
+/// Forwards to a formatter, indenting every line after the first.
+pub(crate) struct Indented<'a, 'b> {
+    pub(crate) f: &'a mut Formatter<'b>,
+    pub(crate) indent: &'static str,
+}
+
+impl core::fmt::Write for Indented<'_, '_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for (index, line) in s.split('\n').enumerate() {
+            if index > 0 {
+                self.f.write_str("\n")?;
+                self.f.write_str(self.indent)?;
+            }
+            self.f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+struct DebugLayer<'a>(&'a Layer, usize, &'a Config);
+
+impl core::fmt::Debug for DebugLayer<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let at = at(self.0, self.1, self.2);
+        if let Some(context) = &self.0.context {
+            write!(f, "{}", context)?;
+            if !at.is_empty() {
+                f.write_str(" ")?;
+            }
+        }
+        write!(f, "{}", at)
+    }
+}
+
+struct Omitted(usize);
+
+impl core::fmt::Debug for Omitted {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "… {} more", self.0)
+    }
+}
+
+pub(crate) struct DebugLayers<'a, 'b>(pub(crate) &'a ReportView<'b>, pub(crate) &'a Config);
+
+impl core::fmt::Debug for DebugLayers<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let (layers, omitted) = shown_layers(self.0, self.1);
+        let mut list = f.debug_list();
+        list.entries(layers.map(|(layer, repeats)| DebugLayer(layer, repeats, self.1)));
+        if omitted > 0 {
+            list.entry(&Omitted(omitted));
+        }
+        list.finish()
+    }
+}
+
+pub(crate) fn write(
+    report: &ReportView<'_>,
+    config: &Config,
+    f: &mut Formatter<'_>,
+) -> core::fmt::Result {
+    match config.verbosity() {
+        Verbosity::Compact => compact(report, config, f),
+        Verbosity::Full => pretty(report, config, f),
+        #[cfg(feature = "serde_json")]
+        Verbosity::Json => write!(f, "{}", crate::wire::view_json(report, config)),
+        _ if f.alternate() => pretty(report, config, f),
+        _ => single_line(report, config, f),
+    }
+}
+
+fn compact(report: &ReportView<'_>, config: &Config, f: &mut Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", report.message())?;
+    if let Some(origin) = report.origin_layer().captured_location() {
+        write!(f, " @ {}", location(origin, config))?;
+    }
+    Ok(())
+}
+
+fn single_line(
+    report: &ReportView<'_>,
+    config: &Config,
+    f: &mut Formatter<'_>,
+) -> core::fmt::Result {
+    let (layers, omitted) = shown_layers(report, config);
+    write!(f, "{}: ", report.message())?;
+    for (index, (layer, repeats)) in layers.enumerate() {
+        if index > 0 {
+            f.write_str(config.separator())?;
+        }
+        let at = at(layer, repeats, config);
+        if let Some(context) = &layer.context {
+            write!(f, "{}", context)?;
+            if !at.is_empty() {
+                f.write_str(" ")?;
+            }
+        }
+        write!(f, "{}", at)?;
+    }
+    if omitted > 0 {
+        write!(f, "{}… {} more", config.separator(), omitted)?;
+    }
+    #[cfg(feature = "uuid")]
+    if let Some(id) = report.request_ref::<crate::id::ReportId>()
+        && !config.stable_output()
+    {
+        write!(f, " (error ID: {})", id)?;
+    }
+    Ok(())
+}
+
+fn snippet(
+    f: &mut Formatter<'_>,
+    palette: Palette,
+    source: &NamedSource,
+    labels: &[&Label],
+) -> core::fmt::Result {
+    let first = labels.first().map_or(0, |label| label.span().start);
+    let (number, line) = source.line_at(first);
+    let column = source.text()[line.start..source.floor(first)]
+        .chars()
+        .count()
+        + 1;
+    let gutter = labels
+        .iter()
+        .map(|label| source.line_at(label.span().start).0)
+        .max()
+        .unwrap_or(number)
+        .to_string()
+        .len();
+    write!(
+        f,
+        "\n\n{:gutter$} ┌─ {}:{}:{}",
+        "",
+        source.name(),
+        number,
+        column
+    )?;
+    write!(f, "\n{:gutter$} │", "")?;
+    let mut previous = None;
+    for label in labels {
+        let span = label.span();
+        let (number, line) = source.line_at(span.start);
+        let text = &source.text()[line.clone()];
+        if previous != Some(number) {
+            write!(f, "\n{:>gutter$} │ {}", number, text)?;
+            previous = Some(number);
+        }
+        let start = source.floor(span.start.clamp(line.start, line.end));
+        let end = source.floor(span.end.clamp(start, line.end));
+        let offset = source.text()[line.start..start].chars().count();
+        let width = source.text()[start..end].chars().count().max(1);
+        write!(f, "\n{:gutter$} │ {:offset$}", "", "")?;
+        palette.error(f, format_args!("{} {}", "^".repeat(width), label.message()))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn pretty(
+    report: &ReportView<'_>,
+    config: &Config,
+    f: &mut Formatter<'_>,
+) -> core::fmt::Result {
+    let palette = Palette::new(config);
+    let (layers, omitted) = shown_layers(report, config);
+    if let Some(ErrorCode(code)) = report.request_ref::<ErrorCode>() {
+        palette.error(f, format_args!("[{}] ", code))?;
+    }
+    palette.error(f, report.message())?;
+    let context_width =
+        |layer: &Layer| layer.context.as_deref().map_or(0, |context| width(context));
+    let (max_width, count) = layers
+        .clone()
+        .fold((0, 0_usize), |(max_width, count), (layer, _)| {
+            (max_width.max(context_width(layer)), count + 1)
+        });
+    let last = (omitted == 0).then(|| count.saturating_sub(1));
+    for (index, (layer, repeats)) in layers.enumerate() {
+        let branch = if Some(index) == last {
+            "╰─"
+        } else {
+            "├─"
+        };
+        write!(f, "\n  {} ", branch)?;
+        let at = at(layer, repeats, config);
+        if max_width > 0 {
+            if let Some(context) = &layer.context {
+                palette.context(f, context)?;
+            }
+            if !at.is_empty() {
+                let padding = max_width - context_width(layer);
+                write!(f, "{:padding$}  ", "", padding = padding)?;
+            }
+        }
+        palette.location(f, at)?;
+        #[cfg(feature = "timestamps")]
+        if !config.stable_output()
+            && !core::ptr::eq(report.origin_layer(), layer)
+            && let (Some(start), Some(at)) = (report.origin_layer().timestamp(), layer.timestamp())
+        {
+            let elapsed = at.instant().duration_since(start.instant());
+            palette.location(f, format_args!(" +{:?}", elapsed))?;
+        }
+        let rail = if Some(index) == last { " " } else { "│" };
+        for attachment in hook::hooked(layer) {
+            write!(f, "\n  {}    {}", rail, attachment)?;
+        }
+    }
+    if omitted > 0 {
+        write!(f, "\n  ╰─ ")?;
+        palette.location(f, format_args!("… {} more", omitted))?;
+    }
+    if let Some(source) = report.request_ref::<NamedSource>() {
+        let mut labels = report.attachments::<Label>().collect::<Vec<_>>();
+        labels.sort_by_key(|label| label.span().start);
+        snippet(f, palette, source, &labels)?;
+    }
+    let mut suggestions = report.attachments::<Suggestion>().peekable();
+    let mut urls = report.attachments::<HelpUrl>().peekable();
+    if suggestions.peek().is_some() || urls.peek().is_some() {
+        f.write_str("\n")?;
+    }
+    for Suggestion(suggestion) in suggestions {
+        f.write_str("\n")?;
+        palette.help(f, "help:")?;
+        write!(f, " {}", suggestion)?;
+    }
+    for HelpUrl(url) in urls {
+        f.write_str("\n")?;
+        palette.help(f, "see:")?;
+        write!(f, " {}", url)?;
+    }
+    let stable = config.stable_output();
+    #[cfg(feature = "uuid")]
+    if let Some(id) = report.request_ref::<crate::id::ReportId>()
+        && !stable
+    {
+        write!(f, "\n\nError ID: {}", id)?;
+    }
+    #[cfg(feature = "thread-info")]
+    if let Some(thread) = report.request_ref::<crate::ThreadInfo>()
+        && !stable
+    {
+        write!(f, "\n\nThread: {}", thread)?;
+    }
+    #[cfg(feature = "sysinfo")]
+    if let Some(system) = report.request_ref::<crate::SystemInfo>()
+        && !stable
+    {
+        write!(f, "\n\nSystem: {}", system)?;
+    }
+    if let Some(metadata) = report.request_ref::<Metadata>() {
+        f.write_str("\n\nMetadata:")?;
+        for (key, value) in metadata.iter() {
+            write!(f, "\n  {}: {}", key, value)?;
+        }
+    }
+    if stable {
+        return Ok(());
+    }
+    #[cfg(feature = "spantrace")]
+    if let Some(span_trace) = report.span_trace() {
+        write!(f, "\n\nSpan trace:\n{}", span_trace)?;
+    }
+    if let Some(backtrace) = report.backtrace() {
+        write!(f, "\n\nStack backtrace:\n{}", backtrace)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextValue, Report};
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    fn groups(layers: &[Layer], root_first: bool) -> Vec<(String, usize)> {
+        Groups { layers, root_first }
+            .map(|(layer, repeats)| (layer.context_str().unwrap_or_default(), repeats))
+            .collect()
+    }
+
+    #[test]
+    fn groups_collapse_runs_of_identical_layers() {
+        let location = Location::caller();
+        let layer = |context: &str| {
+            let mut layer = Layer::new(location);
+            layer.context = Some(ContextValue::from(context.to_string()));
+            layer
+        };
+        let layers = [layer("a"), layer("a"), layer("b"), layer("a")];
+        assert_eq!(
+            groups(&layers, true),
+            [("a".into(), 2), ("b".into(), 1), ("a".into(), 1)]
+        );
+        assert_eq!(
+            groups(&layers, false),
+            [("a".into(), 1), ("b".into(), 1), ("a".into(), 2)]
+        );
+    }
+
+    #[test]
+    fn groups_compare_formatted_contexts_by_their_text() {
+        let location = Location::caller();
+        let layer = |context: ContextValue| {
+            let mut layer = Layer::new(location);
+            layer.context = Some(context);
+            layer
+        };
+        let layers = [
+            layer(ContextValue::new("id 7")),
+            layer(ContextValue::new(format!("id {}", 7))),
+            layer(ContextValue::new(7_u32)),
+            layer(ContextValue::new(7_u32)),
+            layer(ContextValue::new(70_u32)),
+        ];
+        assert_eq!(
+            groups(&layers, true),
+            [("id 7".into(), 2), ("7".into(), 2), ("70".into(), 1)]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn compact_shows_the_origin_inside_a_scope() {
+        let report = crate::scope::in_scope("loading", || Report::msg("boom"));
+        let expected = match report.origin_layer().captured_location() {
+            Some(origin) => format!("boom @ {}", origin),
+            None => "boom".into(),
+        };
+        assert_eq!(report.compact().to_string(), expected);
+        if let Some(scope) = report.layers()[0].captured_location() {
+            assert!(!expected.contains(&format!("{}", scope)));
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{AsDynError, ContextValue, Layer, Report, caller};
+use std::fmt::Display;
+use std::thread;
+use std::time::Duration;
+
+/// How often and how patiently [`retry`] calls an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    delay: Duration,
+    backoff: u32,
+    only_transient: bool,
+}
+
+impl RetryPolicy {
+    /// `max_attempts` tries in total, back to back.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            delay: Duration::ZERO,
+            backoff: 1,
+            only_transient: false,
+        }
+    }
+
+    /// The wait before the second attempt.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Multiplies the wait by `factor` after every attempt.
+    pub fn backoff(mut self, factor: u32) -> Self {
+        self.backoff = factor.max(1);
+        self
+    }
+
+    /// Gives up at once on reports that are not [transient](Report::is_transient).
+    pub fn only_transient(mut self, only_transient: bool) -> Self {
+        self.only_transient = only_transient;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The wait after the failed attempt `attempt`, counting from 1.
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = self.backoff.saturating_pow(attempt.saturating_sub(1));
+        self.delay.saturating_mul(factor)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, waiting 100ms and then 200ms.
+    fn default() -> Self {
+        Self::new(3).delay(Duration::from_millis(100)).backoff(2)
+    }
+}
+
+/// One failed call made by [`retry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    number: u32,
+    message: String,
+    delay: Option<Duration>,
+}
+
+impl Attempt {
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The wait before the next attempt, or `None` for the last one.
+    pub fn delay(&self) -> Option<Duration> {
+        self.delay
+    }
+}
+
+impl Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "attempt {} failed: {}", self.number, self.message)?;
+        if let Some(delay) = self.delay {
+            write!(f, " (retried after {:?})", delay)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every failed attempt in order, attached to the report [`retry`] gives up
+/// with.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Attempts(Vec<Attempt>);
+
+impl Attempts {
+    pub fn iter(&self) -> std::slice::Iter<'_, Attempt> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> IntoIterator for &'a Attempts {
+    type Item = &'a Attempt;
+    type IntoIter = std::slice::Iter<'a, Attempt>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Calls `operation` until it succeeds or `policy` runs out, sleeping the
+/// thread between attempts. The last report is returned with a layer for
+/// each earlier attempt and an [`Attempts`] attachment.
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn retry<T, E, F>(policy: RetryPolicy, mut operation: F) -> Result<T, Report<E>>
+where
+    E: AsDynError + Display + ?Sized,
+    F: FnMut() -> Result<T, Report<E>>,
+{
+    let location = caller();
+    let mut attempts = Vec::new();
+    let mut number = 1;
+    loop {
+        let report = match operation() {
+            Ok(value) => return Ok(value),
+            Err(report) => report,
+        };
+        let last =
+            number >= policy.max_attempts || (policy.only_transient && !report.is_transient());
+        let delay = (!last).then(|| policy.delay_after(number));
+        attempts.push(Attempt {
+            number,
+            message: report.inner().to_string(),
+            delay,
+        });
+        match delay {
+            Some(delay) => {
+                if !delay.is_zero() {
+                    thread::sleep(delay);
+                }
+                number += 1;
+            }
+            None => return Err(give_up(report, attempts, location)),
+        }
+    }
+}
+
+fn give_up<E>(
+    mut report: Report<E>,
+    attempts: Vec<Attempt>,
+    location: &'static std::panic::Location<'static>,
+) -> Report<E>
+where
+    E: ?Sized,
+{
+    for attempt in &attempts[..attempts.len() - 1] {
+        let mut layer = Layer::new(location);
+        layer.context = Some(ContextValue::from(attempt.to_string()));
+        report.push_layer(layer);
+    }
+    let mut layer = Layer::new(location);
+    layer.context = Some(ContextValue::from(format!(
+        "gave up after {} attempts",
+        attempts.len()
+    )));
+    layer.attachments.push(Box::new(Attempts(attempts)));
+    report.push_layer(layer);
+    report
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn attempts(&self) -> Option<&Attempts> {
+        self.request_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringError;
+
+    fn failing(calls: &mut u32, transient: bool) -> Result<(), Report<StringError>> {
+        *calls += 1;
+        let report = Report::msg(format!("fail {}", calls));
+        Err(if transient {
+            report.mark_transient()
+        } else {
+            report
+        })
+    }
+
+    #[test]
+    fn delay_after_backs_off_and_saturates() {
+        let policy = RetryPolicy::new(5)
+            .delay(Duration::from_millis(10))
+            .backoff(3);
+        assert_eq!(policy.delay_after(1), Duration::from_millis(10));
+        assert_eq!(policy.delay_after(2), Duration::from_millis(30));
+        assert_eq!(policy.delay_after(3), Duration::from_millis(90));
+        assert_eq!(policy.delay_after(40), Duration::from_millis(10) * u32::MAX);
+        let policy = policy.delay(Duration::MAX);
+        assert_eq!(policy.delay_after(2), Duration::MAX);
+    }
+
+    #[test]
+    fn gives_up_with_a_layer_per_attempt() {
+        let mut calls = 0;
+        let report = retry(RetryPolicy::new(3), || failing(&mut calls, false))
+            .expect_err("every attempt fails");
+        assert_eq!(calls, 3);
+        assert_eq!(report.inner().as_str(), "fail 3");
+        let contexts = report
+            .layers()
+            .iter()
+            .map(Layer::context_str)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            contexts,
+            [
+                None,
+                Some("attempt 1 failed: fail 1 (retried after 0ns)".into()),
+                Some("attempt 2 failed: fail 2 (retried after 0ns)".into()),
+                Some("gave up after 3 attempts".into()),
+            ]
+        );
+        let attempts = report.attempts().expect("attached");
+        let summary = attempts
+            .iter()
+            .map(|attempt| (attempt.number(), attempt.message(), attempt.delay()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (1, "fail 1", Some(Duration::ZERO)),
+                (2, "fail 2", Some(Duration::ZERO)),
+                (3, "fail 3", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_transient_stops_at_a_permanent_failure() {
+        let policy = RetryPolicy::new(5).only_transient(true);
+        let mut calls = 0;
+        let report = retry(policy, || failing(&mut calls, false)).expect_err("fails");
+        assert_eq!(calls, 1);
+        assert_eq!(report.attempts().map(Attempts::len), Some(1));
+        assert_eq!(
+            report
+                .layers()
+                .last()
+                .and_then(Layer::context_str)
+                .as_deref(),
+            Some("gave up after 1 attempts")
+        );
+        let mut calls = 0;
+        retry(policy, || failing(&mut calls, true)).expect_err("fails");
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn returns_the_first_success() {
+        let mut calls = 0;
+        let value = retry(RetryPolicy::new(3), || {
+            calls += 1;
+            if calls < 2 {
+                return Err(Report::msg("not yet"));
+            }
+            Ok(calls)
+        });
+        assert_eq!(value.ok(), Some(2));
+    }
+}
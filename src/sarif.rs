@@ -0,0 +1,120 @@
+use crate::{Layer, Report, ReportGroup, codes};
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A SARIF location, without `physicalLocation` when the layer has no
+/// captured location.
+fn location(layer: &Layer) -> Value {
+    let mut value = json!({});
+    if let Some(location) = layer.captured_location() {
+        value["physicalLocation"] = json!({
+            "artifactLocation": { "uri": location.file().replace('\\', "/") },
+            "region": {
+                "startLine": location.line(),
+                "startColumn": location.column(),
+            },
+        });
+    }
+    value
+}
+
+fn result<E>(report: &Report<E>) -> Value
+where
+    E: Display + ?Sized,
+{
+    let mut result = json!({
+        "level": "error",
+        "message": { "text": report.inner.to_string() },
+    });
+    if let Some(code) = report.code() {
+        result["ruleId"] = json!(code);
+    }
+    let origin = report.origin_layer();
+    if origin.captured_location().is_some() {
+        result["locations"] = json!([location(origin)]);
+    }
+    let related = report
+        .frames()
+        .enumerate()
+        .map(|(id, layer)| {
+            let mut location = location(layer);
+            location["id"] = json!(id);
+            if let Some(context) = &layer.context {
+                location["message"] = json!({ "text": context.to_string() });
+            }
+            location
+        })
+        .collect::<Vec<_>>();
+    result["relatedLocations"] = json!(related);
+    result
+}
+
+fn log(results: Vec<Value>, codes: BTreeSet<&'static str>) -> Value {
+    let rules = codes
+        .into_iter()
+        .map(|code| {
+            let mut rule = json!({ "id": code });
+            if let Some(explanation) = codes::explain(code) {
+                rule["fullDescription"] = json!({ "text": explanation });
+            }
+            rule
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+impl<E> Report<E>
+where
+    E: Display + ?Sized,
+{
+    pub fn to_sarif(&self) -> Value {
+        log(vec![result(self)], self.code().into_iter().collect())
+    }
+}
+
+impl ReportGroup {
+    pub fn to_sarif(&self) -> Value {
+        log(
+            self.iter().map(result).collect(),
+            self.iter().filter_map(Report::code).collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Report;
+
+    #[test]
+    fn result_points_at_the_origin() {
+        let (report, line) = crate::scope::in_scope("loading", || {
+            (Report::msg("boom").add_context("retry"), line!())
+        });
+        let sarif = report.to_sarif();
+        let result = &sarif["runs"][0]["results"][0];
+        if cfg!(feature = "no-location") {
+            assert!(result["locations"].is_null());
+            assert!(result["relatedLocations"][0]["physicalLocation"].is_null());
+        } else {
+            let region = &result["locations"][0]["physicalLocation"]["region"];
+            assert_eq!(region["startLine"], line);
+        }
+        assert_eq!(result["relatedLocations"][0]["message"]["text"], "retry");
+    }
+}
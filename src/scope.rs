@@ -0,0 +1,186 @@
+use crate::{ContextValue, Layer, LayerStack, caller};
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::Location;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+#[derive(Clone)]
+struct Scope {
+    context: Arc<dyn Display + Send + Sync>,
+    location: &'static Location<'static>,
+}
+
+/// Marks the layers [`extend`] adds, which come before the origin layer.
+pub(crate) struct ScopeLayer;
+
+impl Layer {
+    pub(crate) fn is_scope(&self) -> bool {
+        self.attachments
+            .iter()
+            .any(|attachment| attachment.is::<ScopeLayer>())
+    }
+}
+
+thread_local! {
+    /// Open scopes, outermost first, each with the id of its guard.
+    static SCOPES: RefCell<Vec<(u64, Scope)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+fn push(scope: Scope) -> ScopeGuard {
+    let id = NEXT_ID.with(|next| next.replace(next.get() + 1));
+    let depth = SCOPES.with(|scopes| {
+        let mut scopes = scopes.borrow_mut();
+        scopes.push((id, scope));
+        scopes.len() - 1
+    });
+    ScopeGuard {
+        depth,
+        id,
+        _not_send: PhantomData,
+    }
+}
+
+/// Ends the scope when dropped, along with any entered after it that are
+/// still open. A guard whose scope already ended that way does nothing, so
+/// dropping guards out of order never ends the wrong scope.
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct ScopeGuard {
+    /// How many scopes were entered before this one.
+    depth: usize,
+    id: u64,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        let _ = SCOPES.try_with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            if scopes.get(self.depth).is_some_and(|(id, _)| *id == self.id) {
+                scopes.truncate(self.depth);
+            }
+        });
+    }
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn enter<Ctx>(context: Ctx) -> ScopeGuard
+where
+    Ctx: Display + Send + Sync + 'static,
+{
+    push(Scope {
+        context: Arc::new(context),
+        location: caller(),
+    })
+}
+
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn in_scope<Ctx, R>(context: Ctx, f: impl FnOnce() -> R) -> R
+where
+    Ctx: Display + Send + Sync + 'static,
+{
+    let _guard = enter(context);
+    f()
+}
+
+/// Runs `future` with `context` in scope. The scope is entered around every
+/// poll, so it follows the task across threads on any executor.
+#[cfg_attr(not(feature = "no-location"), track_caller)]
+pub fn with_context<Ctx, F>(context: Ctx, future: F) -> WithContext<F>
+where
+    Ctx: Display + Send + Sync + 'static,
+    F: Future,
+{
+    WithContext {
+        future,
+        scope: Scope {
+            context: Arc::new(context),
+            location: caller(),
+        },
+    }
+}
+
+pub struct WithContext<F> {
+    future: F,
+    scope: Scope,
+}
+
+impl<F> Future for WithContext<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out of `self`; `scope` is not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let _guard = push(this.scope.clone());
+        unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx)
+    }
+}
+
+pub(crate) fn extend(layers: &mut LayerStack) {
+    let _ = SCOPES.try_with(|scopes| {
+        let scopes = scopes.borrow();
+        if scopes.is_empty() {
+            return;
+        }
+        layers.extend(scopes.iter().map(|(_, scope)| {
+            let mut layer = Layer::new(scope.location);
+            layer.context = Some(ContextValue::shared(scope.context.clone()));
+            layer.attachments.push(Box::new(ScopeLayer));
+            layer
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Report;
+
+    #[test]
+    fn origin_is_the_creation_site_inside_a_scope() {
+        let (report, line) = in_scope("loading", || (Report::msg("boom"), line!()));
+        assert_eq!(report.layers().len(), 2);
+        assert!(report.layers()[0].is_scope());
+        assert!(core::ptr::eq(report.origin_layer(), &report.layers()[1]));
+        if !cfg!(feature = "no-location") {
+            assert_eq!(report.origin().line(), line);
+        }
+    }
+
+    fn scopes() -> Vec<Option<String>> {
+        let mut layers = LayerStack::new();
+        extend(&mut layers);
+        layers.iter().map(Layer::context_str).collect()
+    }
+
+    #[test]
+    fn guards_end_their_own_scope() {
+        let outer = enter("outer");
+        let inner = enter("inner");
+        drop(outer);
+        assert!(scopes().is_empty());
+        drop(inner);
+        let _outer = enter("outer");
+        let inner = enter("inner");
+        drop(inner);
+        assert_eq!(scopes(), [Some("outer".into())]);
+    }
+
+    #[test]
+    fn stale_guards_leave_later_scopes_open() {
+        let a = enter("a");
+        let b = enter("b");
+        drop(a);
+        let _x = enter("x");
+        let _y = enter("y");
+        drop(b);
+        assert_eq!(scopes(), [Some("x".into()), Some("y".into())]);
+    }
+}
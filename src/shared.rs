@@ -0,0 +1,93 @@
+use crate::Report;
+use alloc::sync::Arc;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+
+/// A reference-counted report that can be cloned and handed to several
+/// consumers while rendering exactly like the report it wraps.
+pub struct SharedReport<E>
+where
+    E: ?Sized,
+{
+    report: Arc<Report<E>>,
+}
+
+impl<E> SharedReport<E>
+where
+    E: ?Sized,
+{
+    /// Returns the report back if this is the last handle to it.
+    pub fn try_unwrap(this: Self) -> Result<Report<E>, Self> {
+        Arc::try_unwrap(this.report).map_err(|report| Self { report })
+    }
+}
+
+impl<E> Clone for SharedReport<E>
+where
+    E: ?Sized,
+{
+    fn clone(&self) -> Self {
+        Self {
+            report: Arc::clone(&self.report),
+        }
+    }
+}
+
+impl<E> Deref for SharedReport<E>
+where
+    E: ?Sized,
+{
+    type Target = Report<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.report
+    }
+}
+
+impl<E> Debug for SharedReport<E>
+where
+    E: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&*self.report, f)
+    }
+}
+
+impl<E> Display for SharedReport<E>
+where
+    E: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&*self.report, f)
+    }
+}
+
+impl<E> Error for SharedReport<E>
+where
+    E: Error + ?Sized,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.report.source()
+    }
+}
+
+impl<E> From<Report<E>> for SharedReport<E>
+where
+    E: ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        Self {
+            report: Arc::new(report),
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn into_shared(self) -> SharedReport<E> {
+        self.into()
+    }
+}
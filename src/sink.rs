@@ -0,0 +1,125 @@
+use crate::sync::{Mutex, RwLock};
+use crate::{Report, ReportView, hook};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::panic::Location;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static SINK: RwLock<Option<Arc<dyn ReportSink>>> = RwLock::new(None);
+static HAS_SINK: AtomicBool = AtomicBool::new(false);
+
+/// Receives every report as it is created and again when it is finalized,
+/// which happens when it is returned from `main`, turned into a response or
+/// status, or passed to [`Report::finalize`].
+pub trait ReportSink: Send + Sync + 'static {
+    fn created(&self, error_type: &'static str, location: &'static Location<'static>) {
+        let _ = (error_type, location);
+    }
+
+    fn finalized(&self, report: &ReportView<'_>) {
+        let _ = report;
+    }
+}
+
+/// A sink that ignores everything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSink;
+
+impl ReportSink for NoopSink {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SinkEvent {
+    Created {
+        error_type: &'static str,
+        location: &'static Location<'static>,
+    },
+    /// The report rendered with the current configuration.
+    Finalized(String),
+}
+
+/// A sink that keeps every event in memory, for tests. Clones share the
+/// same buffer.
+#[derive(Debug, Clone, Default)]
+pub struct BufferSink {
+    events: Arc<Mutex<Vec<SinkEvent>>>,
+}
+
+impl BufferSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<SinkEvent> {
+        self.events.lock().clone()
+    }
+
+    pub fn take(&self) -> Vec<SinkEvent> {
+        core::mem::take(&mut *self.events.lock())
+    }
+
+    fn push(&self, event: SinkEvent) {
+        self.events.lock().push(event);
+    }
+}
+
+struct Rendered<'a, 'b>(&'a ReportView<'b>);
+
+impl Display for Rendered<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.write_default(f)
+    }
+}
+
+impl ReportSink for BufferSink {
+    fn created(&self, error_type: &'static str, location: &'static Location<'static>) {
+        self.push(SinkEvent::Created {
+            error_type,
+            location,
+        });
+    }
+
+    fn finalized(&self, report: &ReportView<'_>) {
+        self.push(SinkEvent::Finalized(Rendered(report).to_string()));
+    }
+}
+
+pub fn set_global<S>(sink: S)
+where
+    S: ReportSink,
+{
+    *SINK.write() = Some(Arc::new(sink));
+    HAS_SINK.store(true, Ordering::Release);
+}
+
+pub fn clear_global() {
+    HAS_SINK.store(false, Ordering::Release);
+    *SINK.write() = None;
+}
+
+fn sink() -> Option<Arc<dyn ReportSink>> {
+    if !HAS_SINK.load(Ordering::Acquire) {
+        return None;
+    }
+    SINK.read().clone()
+}
+
+pub(crate) fn created(error_type: &'static str, location: &'static Location<'static>) {
+    if let Some(sink) = sink() {
+        sink.created(error_type, location);
+    }
+}
+
+impl<E> Report<E>
+where
+    E: Display + ?Sized,
+{
+    /// Hands the report to the global sink as handled.
+    pub fn finalize(&self) {
+        if let Some(sink) = sink() {
+            let inner = hook::Message(&*self.inner);
+            sink.finalized(&ReportView::new(self, &inner));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::Report;
+use alloc::string::String;
+use core::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedSource {
+    name: String,
+    text: String,
+}
+
+impl NamedSource {
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            text: text.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn floor(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Returns the byte range of the line containing `offset`, without the line break.
+    pub(crate) fn line_at(&self, offset: usize) -> (usize, Range<usize>) {
+        let offset = self.floor(offset);
+        let start = self.text[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let end = self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |index| offset + index);
+        let number = self.text[..start].matches('\n').count() + 1;
+        (number, start..end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    span: Range<usize>,
+    message: String,
+}
+
+impl Label {
+    pub fn new(span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_source(self, source: NamedSource) -> Report<E> {
+        self.attach(source)
+    }
+
+    pub fn label(self, span: Range<usize>, message: impl Into<String>) -> Report<E> {
+        self.attach(Label::new(span, message))
+    }
+
+    pub fn source_code(&self) -> Option<&NamedSource> {
+        self.request_ref::<NamedSource>()
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &Label> {
+        self.attachments::<Label>()
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{AsDynError, Report, caller};
+use sqlx::error::{DatabaseError, ErrorKind};
+use std::fmt::{Display, Formatter};
+use std::panic::Location;
+
+/// Typed fields of a database error, attached by [`SqlxResultExt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatabaseContext {
+    sqlstate: Option<String>,
+    constraint: Option<String>,
+    table: Option<String>,
+    query: Option<String>,
+}
+
+impl DatabaseContext {
+    fn new(error: &sqlx::Error, query: Option<&str>) -> Self {
+        let database = error.as_database_error();
+        Self {
+            sqlstate: database
+                .and_then(|e| e.code())
+                .map(|code| code.into_owned()),
+            constraint: database.and_then(|e| e.constraint()).map(str::to_string),
+            table: database.and_then(|e| e.table()).map(str::to_string),
+            query: query.map(redact_query),
+        }
+    }
+
+    pub fn sqlstate(&self) -> Option<&str> {
+        self.sqlstate.as_deref()
+    }
+
+    pub fn constraint(&self) -> Option<&str> {
+        self.constraint.as_deref()
+    }
+
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    /// The query with its string and numeric literals replaced by `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+}
+
+impl Display for DatabaseContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.query {
+            Some(query) => write!(f, "query `{}`", query)?,
+            None => f.write_str("database error")?,
+        }
+        if let Some(sqlstate) = &self.sqlstate {
+            write!(f, " [{}]", sqlstate)?;
+        }
+        if let Some(constraint) = &self.constraint {
+            write!(f, " on constraint `{}`", constraint)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Length of the quoted literal at the start of `literal`, up to the end
+/// when it is unterminated. `''` is an escaped quote, as is `\'` in `E''`
+/// strings.
+fn quoted_len(literal: &str, backslash_escapes: bool) -> usize {
+    let bytes = literal.as_bytes();
+    let mut index = 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' if backslash_escapes => index += 2,
+            b'\'' if bytes.get(index + 1) == Some(&b'\'') => index += 2,
+            b'\'' => return index + 1,
+            _ => index += 1,
+        }
+    }
+    literal.len()
+}
+
+/// Length of the `$tag$…$tag$` literal at the start of `literal`, if it
+/// opens one rather than a `$1` parameter.
+fn dollar_quoted_len(literal: &str) -> Option<usize> {
+    let end = literal[1..].find('$')? + 1;
+    let tag = &literal[1..end];
+    let valid = tag.chars().next().is_none_or(|c| !c.is_ascii_digit())
+        && tag.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !valid {
+        return None;
+    }
+    let delimiter = &literal[..=end];
+    let body = &literal[end + 1..];
+    Some(match body.find(delimiter) {
+        Some(close) => end + 1 + close + delimiter.len(),
+        None => literal.len(),
+    })
+}
+
+/// Replaces string and numeric literals so values never reach the report.
+/// Handles `''` and `E'\''` escapes and dollar-quoted strings; an
+/// unterminated literal redacts the rest of the query.
+fn redact_query(query: &str) -> String {
+    let mut redacted = String::with_capacity(query.len());
+    let mut rest = query;
+    let mut previous = ' ';
+    while let Some(c) = rest.chars().next() {
+        let literal = match c {
+            '\'' => Some(quoted_len(rest, false)),
+            'E' | 'e' if rest[1..].starts_with('\'') && !is_identifier(previous) => {
+                Some(1 + quoted_len(&rest[1..], true))
+            }
+            '$' if !is_identifier(previous) => dollar_quoted_len(rest),
+            _ if c.is_ascii_digit() && !(is_identifier(previous) || previous == '?') => Some(
+                rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                    .unwrap_or(rest.len()),
+            ),
+            _ => None,
+        };
+        match literal {
+            Some(len) => {
+                redacted.push('?');
+                previous = '?';
+                rest = &rest[len..];
+            }
+            None => {
+                redacted.push(c);
+                previous = c;
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    redacted
+}
+
+fn report(
+    e: sqlx::Error,
+    query: Option<&str>,
+    location: &'static Location<'static>,
+) -> Report<sqlx::Error> {
+    let context = DatabaseContext::new(&e, query);
+    let report = Report::from_boxed_at(Box::new(e), location);
+    if context.sqlstate.is_none() && context.query.is_none() {
+        return report;
+    }
+    report.context(context.clone()).attach(context)
+}
+
+pub trait SqlxResultExt<T> {
+    fn report_db(self) -> Result<T, Report<sqlx::Error>>;
+
+    /// Like [`SqlxResultExt::report_db`], also recording the redacted query.
+    fn report_query(self, query: &str) -> Result<T, Report<sqlx::Error>>;
+}
+
+impl<T> SqlxResultExt<T> for Result<T, sqlx::Error> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_db(self) -> Result<T, Report<sqlx::Error>> {
+        let location = caller();
+        self.map_err(|e| report(e, None, location))
+    }
+
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_query(self, query: &str) -> Result<T, Report<sqlx::Error>> {
+        let location = caller();
+        self.map_err(|e| report(e, Some(query), location))
+    }
+}
+
+impl<E> Report<E>
+where
+    E: AsDynError + ?Sized,
+{
+    /// The first `sqlx` database error in the chain.
+    pub fn database_error(&self) -> Option<&(dyn DatabaseError + 'static)> {
+        self.chain()
+            .filter_map(|e| e.downcast_ref::<sqlx::Error>())
+            .find_map(sqlx::Error::as_database_error)
+    }
+
+    pub fn database_context(&self) -> Option<&DatabaseContext> {
+        self.request_ref()
+    }
+
+    pub fn sqlstate(&self) -> Option<String> {
+        Some(self.database_error()?.code()?.into_owned())
+    }
+
+    pub fn is_unique_violation(&self) -> bool {
+        self.database_kind() == Some(ErrorKind::UniqueViolation)
+    }
+
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.database_kind() == Some(ErrorKind::ForeignKeyViolation)
+    }
+
+    pub fn is_not_null_violation(&self) -> bool {
+        self.database_kind() == Some(ErrorKind::NotNullViolation)
+    }
+
+    pub fn is_check_violation(&self) -> bool {
+        self.database_kind() == Some(ErrorKind::CheckViolation)
+    }
+
+    pub fn is_row_not_found(&self) -> bool {
+        self.chain()
+            .filter_map(|e| e.downcast_ref::<sqlx::Error>())
+            .any(|e| matches!(e, sqlx::Error::RowNotFound))
+    }
+
+    fn database_kind(&self) -> Option<ErrorKind> {
+        self.database_error().map(DatabaseError::kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_plain_literals() {
+        assert_eq!(
+            redact_query("SELECT * FROM t2 WHERE a = 'it''s' AND b > 4.5 AND c = $1"),
+            "SELECT * FROM t2 WHERE a = ? AND b > ? AND c = $1"
+        );
+    }
+
+    #[test]
+    fn redacts_escape_strings() {
+        assert_eq!(
+            redact_query(r"SELECT E'it\'s secret' FROM t WHERE name = 'e'"),
+            "SELECT ? FROM t WHERE name = ?"
+        );
+    }
+
+    #[test]
+    fn redacts_dollar_quoted_strings() {
+        assert_eq!(
+            redact_query("SELECT $$a 'b' c$$, $tag$x $$ y$tag$ FROM t WHERE id = $2"),
+            "SELECT ?, ? FROM t WHERE id = $2"
+        );
+    }
+
+    #[test]
+    fn unterminated_literals_redact_the_rest() {
+        assert_eq!(redact_query("SELECT $q$secret"), "SELECT ?");
+        assert_eq!(redact_query(r"SELECT E'secret\' AND 1"), "SELECT ?");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{AsDynError, NoneError, Report};
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HttpStatus(pub u16);
+
+#[cfg(feature = "std")]
+pub fn for_io_kind(kind: io::ErrorKind) -> u16 {
+    match kind {
+        io::ErrorKind::NotFound => 404,
+        io::ErrorKind::PermissionDenied => 403,
+        io::ErrorKind::AlreadyExists => 409,
+        io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => 400,
+        io::ErrorKind::TimedOut => 504,
+        io::ErrorKind::Unsupported => 501,
+        io::ErrorKind::ConnectionRefused
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::NotConnected => 502,
+        io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => 503,
+        _ => 500,
+    }
+}
+
+pub fn reason_phrase(status: u16) -> Option<&'static str> {
+    Some(match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        412 => "Precondition Failed",
+        413 => "Content Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Content",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => return None,
+    })
+}
+
+pub fn for_error(error: &(dyn Error + 'static)) -> Option<u16> {
+    #[cfg(feature = "std")]
+    if let Some(error) = error.downcast_ref::<io::Error>() {
+        return Some(for_io_kind(error.kind()));
+    }
+    if error.is::<NoneError>() {
+        return Some(404);
+    }
+    if error.is::<core::num::ParseIntError>()
+        || error.is::<core::num::ParseFloatError>()
+        || error.is::<core::str::ParseBoolError>()
+        || error.is::<core::str::Utf8Error>()
+        || error.is::<alloc::string::FromUtf8Error>()
+    {
+        return Some(400);
+    }
+    None
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_status(self, status: u16) -> Report<E> {
+        self.attach(HttpStatus(status))
+    }
+
+    pub fn status(&self) -> Option<u16>
+    where
+        E: AsDynError,
+    {
+        if let Some(HttpStatus(status)) = self.request_ref::<HttpStatus>() {
+            return Some(*status);
+        }
+        self.chain().find_map(for_error)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{Report, caller};
+use futures_core::{Stream, TryStream};
+use std::fmt::Display;
+use std::panic::Location;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct ContextItems<S, Ctx> {
+    stream: S,
+    context: Ctx,
+    location: &'static Location<'static>,
+}
+
+impl<S, Ctx> Stream for ContextItems<S, Ctx>
+where
+    S: TryStream,
+    Ctx: Display + Clone + Send + Sync + 'static,
+{
+    type Item = Result<S::Ok, Report<S::Error>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // SAFETY: `stream` is never moved out of `self`; the other fields are not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(&mut this.stream) }.try_poll_next(cx) {
+            Poll::Ready(Some(Err(e))) => {
+                Poll::Ready(Some(Err(
+                    Report::from_boxed_at(Box::new(e), this.location).context(this.context.clone())
+                )))
+            }
+            Poll::Ready(Some(Ok(item))) => Poll::Ready(Some(Ok(item))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+pub trait StreamReportExt: TryStream + Sized {
+    fn context_items<Ctx>(self, context: Ctx) -> ContextItems<Self, Ctx>
+    where
+        Ctx: Display + Clone + Send + Sync + 'static;
+}
+
+impl<S> StreamReportExt for S
+where
+    S: TryStream,
+{
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn context_items<Ctx>(self, context: Ctx) -> ContextItems<Self, Ctx>
+    where
+        Ctx: Display + Clone + Send + Sync + 'static,
+    {
+        ContextItems {
+            stream: self,
+            context,
+            location: caller(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// Locks that recover from poisoning, backed by spin locks without `std`.
+
+#[cfg(feature = "std")]
+use std::sync as imp;
+
+#[cfg(not(feature = "std"))]
+use spin as imp;
+
+#[cfg(not(any(feature = "std", feature = "spin")))]
+compile_error!("without the `std` feature, enable `spin` for the locks err_report needs");
+
+pub(crate) struct RwLock<T>(imp::RwLock<T>);
+
+impl<T> RwLock<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        Self(imp::RwLock::new(value))
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn read(&self) -> imp::RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn read(&self) -> imp::RwLockReadGuard<'_, T> {
+        self.0.read()
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn write(&self) -> imp::RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn write(&self) -> imp::RwLockWriteGuard<'_, T> {
+        self.0.write()
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Mutex<T>(imp::Mutex<T>);
+
+impl<T> Mutex<T> {
+    #[cfg(feature = "std")]
+    pub(crate) fn lock(&self) -> imp::MutexGuard<'_, T> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn lock(&self) -> imp::MutexGuard<'_, T> {
+        self.0.lock()
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Report;
+use std::fmt::{Display, Formatter};
+use sysinfo::{MemoryRefreshKind, RefreshKind, System};
+
+/// The machine a report was created on, attached by
+/// [`Report::with_system_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemInfo {
+    os: &'static str,
+    os_version: Option<String>,
+    arch: &'static str,
+    total_memory: u64,
+    available_memory: u64,
+    hostname: Option<String>,
+}
+
+impl SystemInfo {
+    pub fn current() -> Self {
+        let system = System::new_with_specifics(
+            RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram()),
+        );
+        Self {
+            os: std::env::consts::OS,
+            os_version: System::long_os_version(),
+            arch: std::env::consts::ARCH,
+            total_memory: system.total_memory(),
+            available_memory: system.available_memory(),
+            hostname: System::host_name(),
+        }
+    }
+
+    /// As in [`std::env::consts::OS`].
+    pub fn os(&self) -> &'static str {
+        self.os
+    }
+
+    pub fn os_version(&self) -> Option<&str> {
+        self.os_version.as_deref()
+    }
+
+    /// As in [`std::env::consts::ARCH`].
+    pub fn arch(&self) -> &'static str {
+        self.arch
+    }
+
+    /// In bytes.
+    pub fn total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    /// In bytes.
+    pub fn available_memory(&self) -> u64 {
+        self.available_memory
+    }
+
+    pub fn hostname(&self) -> Option<&str> {
+        self.hostname.as_deref()
+    }
+}
+
+impl Display for SystemInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const MIB: u64 = 1024 * 1024;
+
+        match &self.os_version {
+            Some(version) => write!(f, "{}, {}", version, self.arch)?,
+            None => write!(f, "{}, {}", self.os, self.arch)?,
+        }
+        write!(
+            f,
+            ", {} of {} MiB memory available",
+            self.available_memory / MIB,
+            self.total_memory / MIB
+        )?;
+        if let Some(hostname) = &self.hostname {
+            write!(f, ", host {}", hostname)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn with_system_info(self) -> Report<E> {
+        self.attach(SystemInfo::current())
+    }
+
+    pub fn system_info(&self) -> Option<&SystemInfo> {
+        self.request_ref()
+    }
+}
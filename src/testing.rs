@@ -0,0 +1,157 @@
+use crate::{AsDynError, Report};
+use alloc::string::ToString;
+use core::error::Error;
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// Whether any layer's context contains `needle`.
+    pub fn contains_context(&self, needle: &str) -> bool {
+        self.layers.iter().any(|layer| {
+            layer
+                .context
+                .as_ref()
+                .is_some_and(|context| context.to_string().contains(needle))
+        })
+    }
+
+    /// Whether any layer was added in `file`, matched by its trailing path
+    /// components so `"config.rs"` matches `src/config.rs`.
+    pub fn has_layer_at(&self, file: &str) -> bool {
+        self.layers.iter().any(|layer| {
+            layer
+                .captured_location()
+                .is_some_and(|location| ends_with_path(location.file(), file))
+        })
+    }
+
+    /// Whether the last error in the chain is a `T`.
+    pub fn root_is<T>(&self) -> bool
+    where
+        E: AsDynError,
+        T: Error + 'static,
+    {
+        self.root_cause().is::<T>()
+    }
+}
+
+/// Whether `suffix` is made of the last whole components of `path`.
+fn ends_with_path(path: &str, suffix: &str) -> bool {
+    path.strip_suffix(suffix)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with(['/', '\\']))
+}
+
+/// Asserts on the structure of a report rather than its rendering, e.g.
+/// `assert_report_matches!(report, root: io::Error, contains_context: "loading config")`.
+/// Accepts `root: Type`, `contains_context: &str`, `layer_at: &str`,
+/// `code: &str` and `layer_count: usize`, in any order.
+#[macro_export]
+macro_rules! assert_report_matches {
+    ($report:expr $(, $($checks:tt)*)?) => {{
+        let report = &$report;
+        $crate::__assert_report_matches!(report; $($($checks)*)?);
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_report_matches {
+    ($report:ident;) => {
+        let _ = $report;
+    };
+    ($report:ident; root: $root:ty $(, $($rest:tt)*)?) => {
+        ::core::assert!(
+            $report.root_is::<$root>(),
+            "expected the root cause to be `{}`:\n{:#?}",
+            ::core::stringify!($root),
+            $report,
+        );
+        $crate::__assert_report_matches!($report; $($($rest)*)?);
+    };
+    ($report:ident; contains_context: $needle:expr $(, $($rest:tt)*)?) => {
+        ::core::assert!(
+            $report.contains_context($needle),
+            "expected a context containing {:?}:\n{:#?}",
+            $needle,
+            $report,
+        );
+        $crate::__assert_report_matches!($report; $($($rest)*)?);
+    };
+    ($report:ident; layer_at: $file:expr $(, $($rest:tt)*)?) => {
+        ::core::assert!(
+            $report.has_layer_at($file),
+            "expected a layer in {:?}:\n{:#?}",
+            $file,
+            $report,
+        );
+        $crate::__assert_report_matches!($report; $($($rest)*)?);
+    };
+    ($report:ident; code: $code:expr $(, $($rest:tt)*)?) => {
+        ::core::assert_eq!(
+            $report.code(),
+            ::core::option::Option::Some($code),
+            "unexpected error code:\n{:#?}",
+            $report,
+        );
+        $crate::__assert_report_matches!($report; $($($rest)*)?);
+    };
+    ($report:ident; layer_count: $count:expr $(, $($rest:tt)*)?) => {
+        ::core::assert_eq!(
+            $report.layers().len(),
+            $count,
+            "unexpected number of layers:\n{:#?}",
+            $report,
+        );
+        $crate::__assert_report_matches!($report; $($($rest)*)?);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StringError;
+
+    #[test]
+    fn paths_match_by_whole_components() {
+        assert!(ends_with_path("src/config.rs", "config.rs"));
+        assert!(ends_with_path("src/config.rs", "src/config.rs"));
+        assert!(ends_with_path("src\\config.rs", "config.rs"));
+        assert!(!ends_with_path("src/config.rs", "fig.rs"));
+        assert!(!ends_with_path("src/config.rs", "main.rs"));
+    }
+
+    #[test]
+    fn layers_are_found_by_file() {
+        let report = Report::msg("boom");
+        let located = !cfg!(feature = "no-location");
+        assert_eq!(report.has_layer_at("testing.rs"), located);
+        assert_eq!(report.has_layer_at("src/testing.rs"), located);
+        assert!(!report.has_layer_at("ting.rs"));
+    }
+
+    #[test]
+    fn every_check_passes_on_a_matching_report() {
+        let report = Report::msg("boom")
+            .with_code("E_TESTING")
+            .add_context("loading config");
+        crate::assert_report_matches!(
+            report,
+            root: StringError,
+            contains_context: "loading",
+            code: "E_TESTING",
+            layer_count: 2,
+        );
+        if !cfg!(feature = "no-location") {
+            crate::assert_report_matches!(report, layer_at: "testing.rs");
+        }
+        crate::assert_report_matches!(report);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a context containing")]
+    fn a_failed_check_panics_with_the_report() {
+        let report = Report::msg("boom").add_context("loading config");
+        crate::assert_report_matches!(report, layer_count: 2, contains_context: "saving");
+    }
+}
@@ -0,0 +1,60 @@
+use crate::Report;
+use std::fmt::{Display, Formatter};
+use std::thread::ThreadId;
+
+/// The thread, and with the `tokio` feature the task, a report was created on.
+#[derive(Debug, Clone)]
+pub struct ThreadInfo {
+    name: Option<String>,
+    id: ThreadId,
+    #[cfg(feature = "tokio")]
+    task_id: Option<tokio::task::Id>,
+}
+
+impl ThreadInfo {
+    pub(crate) fn current() -> Self {
+        let thread = std::thread::current();
+        Self {
+            name: thread.name().map(str::to_string),
+            id: thread.id(),
+            #[cfg(feature = "tokio")]
+            task_id: tokio::task::try_id(),
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn id(&self) -> ThreadId {
+        self.id
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn task_id(&self) -> Option<tokio::task::Id> {
+        self.task_id
+    }
+}
+
+impl Display for ThreadInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({:?})", name, self.id)?,
+            None => write!(f, "{:?}", self.id)?,
+        }
+        #[cfg(feature = "tokio")]
+        if let Some(task_id) = self.task_id {
+            write!(f, ", task {}", task_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn thread_info(&self) -> Option<&ThreadInfo> {
+        self.request_ref()
+    }
+}
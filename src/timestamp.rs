@@ -0,0 +1,43 @@
+use crate::{Layer, Report};
+use std::time::{Instant, SystemTime};
+
+/// When a layer was added, captured by [`Layer::new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    system: SystemTime,
+    instant: Instant,
+}
+
+impl Timestamp {
+    pub(crate) fn now() -> Self {
+        Self {
+            system: SystemTime::now(),
+            instant: Instant::now(),
+        }
+    }
+
+    pub fn system(&self) -> SystemTime {
+        self.system
+    }
+
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+}
+
+impl Layer {
+    pub fn timestamp(&self) -> Option<&Timestamp> {
+        self.attachments
+            .iter()
+            .find_map(|attachment| attachment.downcast_ref())
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn created_at(&self) -> Option<SystemTime> {
+        self.origin_layer().timestamp().map(Timestamp::system)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{AnyError, ContextValue, Layer, Report, caller};
+use http::{Method, Request, Uri};
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::panic::Location;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// The request a service error happened on, attached by [`ReportLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestInfo {
+    method: Method,
+    uri: Uri,
+}
+
+impl RequestInfo {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn uri(&self) -> &Uri {
+        &self.uri
+    }
+}
+
+impl Display for RequestInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.uri)
+    }
+}
+
+/// Middleware that turns the inner service's errors into
+/// `Report<AnyError>` with the request's method and URI as a layer. The
+/// layer's location is where the `ReportLayer` was created.
+#[derive(Debug, Clone, Copy)]
+pub struct ReportLayer {
+    location: &'static Location<'static>,
+    finalize: bool,
+}
+
+impl ReportLayer {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    pub fn new() -> Self {
+        Self {
+            location: caller(),
+            finalize: false,
+        }
+    }
+
+    /// Also hands every error to the global [`sink`](crate::sink).
+    pub fn finalize(mut self, finalize: bool) -> Self {
+        self.finalize = finalize;
+        self
+    }
+}
+
+impl Default for ReportLayer {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> tower_layer::Layer<S> for ReportLayer {
+    type Service = ReportService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReportService {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportService<S> {
+    inner: S,
+    layer: ReportLayer,
+}
+
+impl<S, B> Service<Request<B>> for ReportService<S>
+where
+    S: Service<Request<B>>,
+    S::Error: Into<Report<AnyError>>,
+{
+    type Response = S::Response;
+    type Error = Report<AnyError>;
+    type Future = ResponseFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let info = RequestInfo {
+            method: request.method().clone(),
+            uri: request.uri().clone(),
+        };
+        ResponseFuture {
+            future: self.inner.call(request),
+            info: Some(info),
+            layer: self.layer,
+        }
+    }
+}
+
+pub struct ResponseFuture<F> {
+    future: F,
+    info: Option<RequestInfo>,
+    layer: ReportLayer,
+}
+
+impl<F, T, E> Future for ResponseFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Into<Report<AnyError>>,
+{
+    type Output = Result<T, Report<AnyError>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out of `self`; the other fields are
+        // not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        match unsafe { Pin::new_unchecked(&mut this.future) }.poll(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(Ok(value)),
+            Poll::Ready(Err(e)) => {
+                let info = this
+                    .info
+                    .take()
+                    .expect("ResponseFuture polled after completion");
+                let mut report = e.into();
+                let mut layer = Layer::new(this.layer.location);
+                layer.context = Some(ContextValue::new(info.clone()));
+                layer.attachments.push(Box::new(info));
+                report.add_layer(layer);
+                if this.layer.finalize {
+                    report.finalize();
+                }
+                Poll::Ready(Err(report))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn request_info(&self) -> Option<&RequestInfo> {
+        self.request_ref()
+    }
+}
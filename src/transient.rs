@@ -0,0 +1,68 @@
+use crate::sync::RwLock;
+use crate::{AsDynError, Report};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+type Classifier = Box<dyn Fn(&(dyn Error + 'static)) -> bool + Send + Sync>;
+
+static CLASSIFIERS: RwLock<Vec<Classifier>> = RwLock::new(Vec::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transient;
+
+pub fn register_classifier<F>(classifier: F)
+where
+    F: Fn(&(dyn Error + 'static)) -> bool + Send + Sync + 'static,
+{
+    CLASSIFIERS.write().push(Box::new(classifier));
+}
+
+#[cfg(feature = "std")]
+pub fn is_transient_io_kind(kind: io::ErrorKind) -> bool {
+    matches!(
+        kind,
+        io::ErrorKind::TimedOut
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+    )
+}
+
+pub fn for_error(error: &(dyn Error + 'static)) -> bool {
+    #[cfg(feature = "std")]
+    if let Some(error) = error.downcast_ref::<io::Error>()
+        && is_transient_io_kind(error.kind())
+    {
+        return true;
+    }
+    #[cfg(feature = "reqwest")]
+    if let Some(error) = error.downcast_ref::<reqwest::Error>()
+        && (error.is_timeout() || error.is_connect())
+    {
+        return true;
+    }
+    CLASSIFIERS
+        .read()
+        .iter()
+        .any(|classifier| classifier(error))
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    pub fn mark_transient(self) -> Report<E> {
+        self.attach(Transient)
+    }
+
+    pub fn is_transient(&self) -> bool
+    where
+        E: AsDynError,
+    {
+        self.request_ref::<Transient>().is_some() || self.chain().any(for_error)
+    }
+}
@@ -0,0 +1,172 @@
+use crate::dedup::Repeated;
+use crate::{ContextValue, Layer, Report};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use core::any::Any;
+
+/// Marks the layer standing in for layers dropped by
+/// [`ConfigBuilder::max_layers`](crate::config::ConfigBuilder::max_layers).
+pub(crate) struct LayersOmitted(usize);
+
+impl Layer {
+    pub(crate) fn omitted(&self) -> Option<usize> {
+        self.attachments
+            .iter()
+            .find_map(|attachment| attachment.downcast_ref::<LayersOmitted>())
+            .map(|omitted| omitted.0)
+    }
+}
+
+impl<E> Report<E>
+where
+    E: ?Sized,
+{
+    /// How many layers were dropped to stay within the configured cap.
+    pub fn omitted_layers(&self) -> usize {
+        self.layers.iter().filter_map(Layer::omitted).sum()
+    }
+
+    /// Folds the oldest layer between the origin and the newest layer into
+    /// the omission marker, creating the marker from the two oldest if there
+    /// is none yet. Once too few are left there, folds scope layers instead;
+    /// the origin and the newest layer are always kept.
+    pub(crate) fn omit_layer(&mut self) {
+        let origin = crate::origin_index(&self.layers);
+        let newest = self.layers.len() - 1;
+        let start = if newest.saturating_sub(origin + 1) >= 2 {
+            origin + 1
+        } else if origin >= 2 {
+            0
+        } else {
+            return;
+        };
+        let (mut marker, omitted) = match self.layers[start].omitted() {
+            Some(omitted) => (self.layers.remove(start), omitted),
+            None => {
+                let location = self.layers[start].location;
+                let marker = Layer {
+                    context: None,
+                    location,
+                    attachments: Vec::new(),
+                };
+                (marker, 0)
+            }
+        };
+        let dropped = if omitted == 0 { 2 } else { 1 };
+        for _ in 0..dropped {
+            let layer = self.layers.remove(start);
+            fold(&mut marker, layer.attachments);
+        }
+        let omitted = omitted + dropped;
+        marker
+            .attachments
+            .retain(|attachment| !attachment.is::<LayersOmitted>());
+        marker.attachments.push(Box::new(LayersOmitted(omitted)));
+        marker.context = Some(ContextValue::from(format!(
+            "… {} layers omitted …",
+            omitted
+        )));
+        self.layers.insert(start, marker);
+    }
+}
+
+/// Bookkeeping that only means something on the layer it was added to.
+fn per_layer(attachment: &(dyn Any + Send + Sync)) -> bool {
+    #[cfg(feature = "timestamps")]
+    if attachment.is::<crate::Timestamp>() {
+        return true;
+    }
+    attachment.is::<LayersOmitted>() || attachment.is::<Repeated>()
+}
+
+/// Moves a folded layer's attachments into the marker, keeping one of each
+/// type so `request_ref` still finds codes, metadata and the like while the
+/// marker stays the same size however many layers it stands for. Folded
+/// layers are newer than the marker's contents, so their values win.
+fn fold(marker: &mut Layer, attachments: Vec<Box<dyn Any + Send + Sync>>) {
+    for attachment in attachments {
+        if per_layer(&*attachment) {
+            continue;
+        }
+        let type_id = (*attachment).type_id();
+        match marker
+            .attachments
+            .iter()
+            .position(|kept| (**kept).type_id() == type_id)
+        {
+            Some(index) => marker.attachments[index] = attachment,
+            None => marker.attachments.push(attachment),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Report;
+
+    fn contexts<E: ?Sized>(report: &Report<E>) -> Vec<Option<String>> {
+        report
+            .layers()
+            .iter()
+            .map(|layer| layer.context_str())
+            .collect()
+    }
+
+    #[test]
+    fn folds_layers_after_the_origin_first() {
+        let mut report = Report::msg("boom")
+            .add_context("a")
+            .add_context("b")
+            .add_context("c");
+        report.omit_layer();
+        assert_eq!(
+            contexts(&report),
+            [None, Some("… 2 layers omitted …".into()), Some("c".into())]
+        );
+        assert_eq!(report.omitted_layers(), 2);
+    }
+
+    #[test]
+    fn marker_storage_stays_bounded() {
+        let mut report = Report::msg("boom").attach("origin");
+        for attempt in 0..10_000_u32 {
+            report = report.add_context("retry").attach(attempt);
+            if report.layers().len() > 4 {
+                report.omit_layer();
+            }
+        }
+        assert_eq!(report.layers().len(), 4);
+        assert_eq!(report.omitted_layers(), 10_000 - 2);
+        let attachments = report
+            .layers()
+            .iter()
+            .map(|layer| layer.attachments.len())
+            .sum::<usize>();
+        assert!(attachments <= 12, "{} attachments", attachments);
+        assert_eq!(report.request_ref::<u32>(), Some(&9_999));
+        let marker = report
+            .layers()
+            .iter()
+            .find(|layer| layer.omitted().is_some());
+        let kept = marker.map(|marker| marker.attachments.iter().filter(|a| a.is::<u32>()).count());
+        assert_eq!(kept, Some(1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn keeps_the_origin_inside_scopes() {
+        let (mut report, line) = crate::scope::in_scope("s1", || {
+            crate::scope::in_scope("s2", || (Report::msg("boom").add_context("a"), line!()))
+        });
+        report.omit_layer();
+        assert_eq!(
+            contexts(&report),
+            [Some("… 2 layers omitted …".into()), None, Some("a".into())]
+        );
+        assert!(core::ptr::eq(report.origin_layer(), &report.layers()[1]));
+        if !cfg!(feature = "no-location") {
+            assert_eq!(report.origin().line(), line);
+        }
+    }
+}
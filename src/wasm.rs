@@ -0,0 +1,77 @@
+use crate::{AnyError, Report, caller};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use wasm_bindgen::{JsCast, JsError, JsValue};
+
+/// Throws as a JavaScript `Error` whose message is the pretty rendering.
+impl<E> From<Report<E>> for JsValue
+where
+    E: Display + ?Sized,
+{
+    fn from(report: Report<E>) -> Self {
+        report.finalize();
+        JsError::new(&format!("{:#}", report)).into()
+    }
+}
+
+/// A JavaScript exception caught at the boundary. `JsValue` is neither
+/// `Send` nor `Sync`, so only its name and message are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsException {
+    name: Option<String>,
+    message: String,
+}
+
+impl JsException {
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for JsException {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{}: {}", name, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl Error for JsException {}
+
+impl From<JsValue> for JsException {
+    fn from(value: JsValue) -> Self {
+        if let Some(error) = value.dyn_ref::<js_sys::Error>() {
+            return Self {
+                name: error.name().as_string(),
+                message: error.message().into(),
+            };
+        }
+        Self {
+            name: None,
+            message: value.as_string().unwrap_or_else(|| format!("{:?}", value)),
+        }
+    }
+}
+
+pub trait JsResultExt<T> {
+    /// Converts a caught exception, keeping its name and message.
+    fn report_js(self) -> Result<T, Report<AnyError>>;
+}
+
+impl<T> JsResultExt<T> for Result<T, JsValue> {
+    #[cfg_attr(not(feature = "no-location"), track_caller)]
+    fn report_js(self) -> Result<T, Report<AnyError>> {
+        let location = caller();
+        self.map_err(|value| {
+            Report::from_boxed_at(
+                Box::new(JsException::from(value)) as Box<AnyError>,
+                location,
+            )
+        })
+    }
+}
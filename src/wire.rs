@@ -0,0 +1,274 @@
+#[cfg(feature = "serde_json")]
+use crate::ReportView;
+#[cfg(feature = "serde_json")]
+use crate::codes::ErrorCode;
+use crate::global::Metadata;
+use crate::{Config, LayerData, Report};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+#[derive(Serialize, Deserialize)]
+struct WireReport {
+    message: String,
+    layers: Vec<LayerData>,
+    sources: Vec<String>,
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    metadata: Metadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+struct DeserializedSource {
+    message: String,
+    source: Option<Box<DeserializedSource>>,
+}
+
+impl Debug for DeserializedSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.message, f)
+    }
+}
+
+impl Display for DeserializedSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for DeserializedSource {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+pub struct DeserializedReport {
+    message: String,
+    layers: Vec<LayerData>,
+    source: Option<Box<DeserializedSource>>,
+    metadata: Metadata,
+    code: Option<String>,
+    id: Option<String>,
+}
+
+impl DeserializedReport {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn layers(&self) -> &[LayerData] {
+        &self.layers
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn sources(&self) -> Vec<String> {
+        std::iter::successors(self.source.as_deref(), |source| source.source.as_deref())
+            .map(|source| source.message.clone())
+            .collect()
+    }
+}
+
+impl From<WireReport> for DeserializedReport {
+    fn from(wire: WireReport) -> Self {
+        let source = wire
+            .sources
+            .into_iter()
+            .rev()
+            .fold(None, |source, message| {
+                Some(Box::new(DeserializedSource { message, source }))
+            });
+        Self {
+            message: wire.message,
+            layers: wire.layers,
+            source,
+            metadata: wire.metadata,
+            code: wire.code,
+            id: wire.id,
+        }
+    }
+}
+
+impl Debug for DeserializedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeserializedReport")
+            .field("message", &self.message)
+            .field("layers", &self.layers)
+            .field("sources", &self.sources())
+            .field("metadata", &self.metadata)
+            .field("code", &self.code)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Display for DeserializedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.message)?;
+        for (index, layer) in self.layers.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", layer)?;
+        }
+        if let Some(id) = &self.id {
+            write!(f, " (error ID: {})", id)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DeserializedReport {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl Serialize for DeserializedReport {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        WireReport {
+            message: self.message.clone(),
+            layers: self.layers.clone(),
+            sources: self.sources(),
+            metadata: self.metadata.clone(),
+            code: self.code.clone(),
+            id: self.id.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DeserializedReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        WireReport::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl<E> Serialize for Report<E>
+where
+    E: Error + ?Sized,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let config = Config::current();
+        WireReport {
+            message: self.inner.to_string(),
+            layers: self
+                .frames()
+                .map(|layer| LayerData::rendered(layer, &config))
+                .collect(),
+            sources: self.sources().map(|source| source.to_string()).collect(),
+            metadata: self.metadata().cloned().unwrap_or_default(),
+            code: self.code().map(str::to_string),
+            #[cfg(feature = "uuid")]
+            id: self.id().map(|id| id.to_string()),
+            #[cfg(not(feature = "uuid"))]
+            id: None,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+pub(crate) fn view_json(view: &ReportView<'_>, config: &Config) -> serde_json::Value {
+    let wire = WireReport {
+        message: view.message().to_string(),
+        layers: view
+            .layers()
+            .iter()
+            .rev()
+            .map(|layer| LayerData::rendered(layer, config))
+            .collect(),
+        sources: view.sources().map(|source| source.to_string()).collect(),
+        metadata: view.request_ref::<Metadata>().cloned().unwrap_or_default(),
+        code: view
+            .request_ref::<ErrorCode>()
+            .map(|code| code.0.to_string()),
+        #[cfg(feature = "uuid")]
+        id: view
+            .request_ref::<crate::id::ReportId>()
+            .filter(|_| !config.stable_output())
+            .map(ToString::to_string),
+        #[cfg(not(feature = "uuid"))]
+        id: None,
+    };
+    serde_json::to_value(wire).expect("report wire format is always representable as JSON")
+}
+
+#[cfg(feature = "serde_json")]
+impl<E> Report<E>
+where
+    E: Error + ?Sized,
+{
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("report wire format is always representable as JSON")
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+    use crate::StringError;
+
+    #[derive(Debug)]
+    struct Outer(StringError);
+
+    impl Display for Outer {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            f.write_str("outer")
+        }
+    }
+
+    impl Error for Outer {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn sources_round_trip_with_an_added_cause() {
+        let report =
+            Report::new(Outer(StringError::new("disk"))).with_cause(StringError::new("extra"));
+        let json = serde_json::to_string(&report).expect("serializes");
+        let report: DeserializedReport = serde_json::from_str(&json).expect("deserializes");
+        assert_eq!(report.message(), "outer");
+        assert_eq!(report.sources(), ["extra", "disk"]);
+    }
+
+    #[test]
+    fn view_json_lists_added_causes_and_blanks_unstable_fields() {
+        let report =
+            Report::new(Outer(StringError::new("disk"))).with_cause(StringError::new("extra"));
+        let message = crate::hook::Message(&*report.inner);
+        let view = ReportView::new(&report, &message);
+        let json = view_json(&view, &Config::current().with_stable_output());
+        assert_eq!(json["sources"], serde_json::json!(["extra"]));
+        assert_eq!(json["layers"][0]["file"], "");
+        assert_eq!(json["layers"][0]["line"], 0);
+        assert!(json.get("id").is_none());
+    }
+}